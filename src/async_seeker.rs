@@ -0,0 +1,732 @@
+use crate::error::{Error, ErrorKind, Result};
+use crate::finder::Finder;
+use crate::seeker::{Dir, MatchMode, State, DEFAULT_CHUNK_SIZE};
+use crate::window::{Refill, Step, Window};
+use futures_core::Stream;
+use std::future;
+use std::io::{self, SeekFrom};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+
+/// Seeker that can seek the occurences of a given byte slice within an asynchronous stream of
+/// bytes.
+///
+/// `AsyncByteSeeker` is the asynchronous counterpart of [`ByteSeeker`], for byte streams that
+/// implement tokio's `AsyncRead` and `AsyncSeek`. It searches the same way, reading the byte
+/// stream chunk by chunk, and keeps a cursor for each direction, so that forward and backward
+/// searches move towards each other and never cross.
+///
+/// This struct is only available with the `tokio` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// use byteseeker::AsyncByteSeeker;
+/// use std::io::Cursor;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let bytes = b"0\n1\n2";
+/// let mut seeker = AsyncByteSeeker::new(Cursor::new(bytes)).await.unwrap();
+///
+/// assert_eq!(seeker.seek(b"\n").await.unwrap(), 1);
+/// assert_eq!(seeker.seek_back(b"\n").await.unwrap(), 3);
+/// assert_eq!(seeker.seek(b"\n").await.is_err(), true);
+/// # });
+/// ```
+///
+/// [`ByteSeeker`]: struct.ByteSeeker.html
+#[derive(Debug)]
+pub struct AsyncByteSeeker<R: AsyncRead + AsyncSeek + Unpin> {
+    inner: R,
+    // The current position of `inner`, if known.
+    stream_pos: Option<u64>,
+    // A window of the underlying stream, holding the bytes read last.
+    window: Window,
+    // The bytes the window is still waiting for, which must be read before it can be searched.
+    refill: Option<Refill>,
+    // Whether a seek of `inner` has been started but not completed yet.
+    seeking: bool,
+    len: u64,
+    // The maximum length of the byte slices to seek.
+    cap: usize,
+    // The number of bytes read from `inner` at once, besides the carried over ones.
+    chunk_size: usize,
+    state: State,
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin> AsyncByteSeeker<R> {
+    /// Creates a new `AsyncByteSeeker` that wraps a byte stream that implements `AsyncRead` and
+    /// `AsyncSeek`.
+    ///
+    /// # Errors
+    ///
+    /// If measuring the length of the underlying byte stream fails, an error variant of
+    /// `ErrorKind::Io` will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let seeker = AsyncByteSeeker::new(Cursor::new(b"lorem ipsum")).await.unwrap();
+    /// assert_eq!(seeker.len(), 11);
+    /// # });
+    /// ```
+    pub async fn new(stream: R) -> Result<Self> {
+        AsyncByteSeeker::with_capacity(stream, DEFAULT_CHUNK_SIZE).await
+    }
+
+    /// Creates a new `AsyncByteSeeker` with the specified capacity, which is the maximum length
+    /// of the byte slices it can seek.
+    ///
+    /// # Errors
+    ///
+    /// See [`new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let cursor = Cursor::new(b"lorem ipsum");
+    /// let seeker = AsyncByteSeeker::with_capacity(cursor, 5).await.unwrap();
+    /// assert_eq!(seeker.capacity(), 5);
+    /// # });
+    /// ```
+    ///
+    /// [`new`]: #method.new
+    pub async fn with_capacity(mut stream: R, cap: usize) -> Result<Self> {
+        let mut seeking = false;
+        let len = future::poll_fn(|cx| poll_seek(&mut stream, &mut seeking, cx, SeekFrom::End(0)))
+            .await?;
+
+        Ok(Self {
+            inner: stream,
+            stream_pos: Some(len),
+            // The buffer is only allocated once the first search knows how much it needs.
+            window: Window::new(0),
+            refill: None,
+            seeking: false,
+            len,
+            cap,
+            chunk_size: DEFAULT_CHUNK_SIZE.max(cap),
+            state: State { lpos: 0, rpos: len },
+        })
+    }
+
+    /// Returns the length of the underlying byte stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let seeker = AsyncByteSeeker::new(Cursor::new(b"lorem ipsum")).await.unwrap();
+    /// assert_eq!(seeker.len(), 11);
+    /// # });
+    /// ```
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the underlying byte stream is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let seeker = AsyncByteSeeker::new(Cursor::new(b"")).await.unwrap();
+    /// assert!(seeker.is_empty());
+    /// # });
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the capacity of this `AsyncByteSeeker`, which is the maximum length of the byte
+    /// slices it can seek.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let cursor = Cursor::new(b"lorem ipsum");
+    /// let seeker = AsyncByteSeeker::with_capacity(cursor, 5).await.unwrap();
+    /// assert_eq!(seeker.capacity(), 5);
+    /// # });
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Returns the number of bytes this `AsyncByteSeeker` reads from the underlying byte stream
+    /// at once, besides the bytes carried over from the previous chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let cursor = Cursor::new(b"lorem ipsum");
+    /// let seeker = AsyncByteSeeker::with_capacity(cursor, 5).await.unwrap();
+    /// assert_eq!(seeker.chunk_size(), 1024);
+    /// # });
+    /// ```
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Sets the number of bytes this `AsyncByteSeeker` reads from the underlying byte stream at
+    /// once.
+    ///
+    /// See [`ByteSeeker::set_chunk_size`] for more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero. Larger chunk sizes, even `usize::MAX`, are fine: no more
+    /// bytes are buffered than the underlying byte stream holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let cursor = Cursor::new(b"lorem ipsum");
+    /// let mut seeker = AsyncByteSeeker::with_capacity(cursor, 5).await.unwrap();
+    /// seeker.set_chunk_size(64 * 1024);
+    ///
+    /// assert_eq!(seeker.capacity(), 5);
+    /// assert_eq!(seeker.chunk_size(), 64 * 1024);
+    /// assert_eq!(seeker.seek(b"ipsum").await.unwrap(), 6);
+    /// # });
+    /// ```
+    ///
+    /// [`ByteSeeker::set_chunk_size`]: struct.ByteSeeker.html#method.set_chunk_size
+    pub fn set_chunk_size(&mut self, chunk_size: usize) {
+        assert!(chunk_size > 0, "the chunk size must be positive");
+        self.chunk_size = chunk_size;
+    }
+
+    /// Returns the offset where the next forward search starts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut seeker = AsyncByteSeeker::new(Cursor::new(b"0\n1\n")).await.unwrap();
+    /// assert_eq!(seeker.position(), 0);
+    /// assert_eq!(seeker.seek(b"\n").await.unwrap(), 1);
+    /// assert_eq!(seeker.position(), 2);
+    /// # });
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.state.lpos
+    }
+
+    /// Returns the offset (exclusive) where the next backward search ends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut seeker = AsyncByteSeeker::new(Cursor::new(b"0\n1\n")).await.unwrap();
+    /// assert_eq!(seeker.back_position(), 4);
+    /// assert_eq!(seeker.seek_back(b"\n").await.unwrap(), 3);
+    /// assert_eq!(seeker.back_position(), 3);
+    /// # });
+    /// ```
+    pub fn back_position(&self) -> u64 {
+        self.state.rpos
+    }
+
+    /// Resets the state of the `AsyncByteSeeker` to its original, so that searches start from
+    /// either end of the underlying byte stream again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut seeker = AsyncByteSeeker::new(Cursor::new(b"0\n0")).await.unwrap();
+    /// assert_eq!(seeker.seek(b"0").await.unwrap(), 0);
+    /// seeker.reset();
+    /// assert_eq!(seeker.seek(b"0").await.unwrap(), 0);
+    /// # });
+    /// ```
+    pub fn reset(&mut self) {
+        self.state = State {
+            lpos: 0,
+            rpos: self.len,
+        };
+    }
+
+    /// Seeks the given bytes **forwards**, and returns the offset (relative to the start of the
+    /// underlying byte stream) if the given bytes were found.
+    ///
+    /// Like [`ByteSeeker::seek`], consecutive calls find consecutive occurences of the given
+    /// bytes, which do not overlap each other. If the returned future is dropped before it
+    /// completes, the state of this `AsyncByteSeeker` is left untouched.
+    ///
+    /// # Errors
+    ///
+    /// If the length of the given bytes is zero or greater than the capacity of this
+    /// `AsyncByteSeeker`, an error variant of `ErrorKind::UnsupportedLength` will be returned.
+    /// If the given bytes were not found, an error variant of `ErrorKind::ByteNotFound` will be
+    /// returned. If any other I/O errors were encountered, an error variant of `ErrorKind::Io`
+    /// will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut seeker = AsyncByteSeeker::new(Cursor::new(b"0\r\n1\r\n")).await.unwrap();
+    /// assert_eq!(seeker.seek(b"\r\n").await.unwrap(), 1);
+    /// assert_eq!(seeker.seek(b"\r\n").await.unwrap(), 4);
+    /// assert_eq!(seeker.seek(b"\r\n").await.is_err(), true);
+    /// # });
+    /// ```
+    ///
+    /// [`ByteSeeker::seek`]: struct.ByteSeeker.html#method.seek
+    pub async fn seek(&mut self, bytes: &[u8]) -> Result<u64> {
+        self.buf_seek(bytes, Dir::Start).await
+    }
+
+    /// Seeks the given bytes **backwards**, and returns the offset (relative to the start of the
+    /// underlying byte stream) if the given bytes were found.
+    ///
+    /// # Errors
+    ///
+    /// See [`seek`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut seeker = AsyncByteSeeker::new(Cursor::new(b"0\r\n1\r\n")).await.unwrap();
+    /// assert_eq!(seeker.seek_back(b"\r\n").await.unwrap(), 4);
+    /// assert_eq!(seeker.seek_back(b"\r\n").await.unwrap(), 1);
+    /// assert_eq!(seeker.seek_back(b"\r\n").await.is_err(), true);
+    /// # });
+    /// ```
+    ///
+    /// [`seek`]: #method.seek
+    pub async fn seek_back(&mut self, bytes: &[u8]) -> Result<u64> {
+        self.buf_seek(bytes, Dir::End).await
+    }
+
+    /// Seeks the nth occurence of the given bytes **forwards**, and returns the offset (relative
+    /// to the start of the underlying byte stream) if the given bytes were found.
+    ///
+    /// `nth` counts from one, like [`ByteSeeker::seek_nth`].
+    ///
+    /// # Errors
+    ///
+    /// See [`seek`]. An error variant of `ErrorKind::ByteNotFound` is also returned if `nth` is
+    /// zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut seeker = AsyncByteSeeker::new(Cursor::new(b"\n\n\n\n\n")).await.unwrap();
+    /// assert_eq!(seeker.seek_nth(b"\n\n", 2).await.unwrap(), 2);
+    /// assert_eq!(seeker.seek_nth(b"\n\n", 2).await.is_err(), true);
+    /// # });
+    /// ```
+    ///
+    /// [`ByteSeeker::seek_nth`]: struct.ByteSeeker.html#method.seek_nth
+    /// [`seek`]: #method.seek
+    pub async fn seek_nth(&mut self, bytes: &[u8], nth: usize) -> Result<u64> {
+        self.nth_seek(bytes, nth, Dir::Start).await
+    }
+
+    /// Seeks the nth occurence of the given bytes **backwards**, and returns the offset
+    /// (relative to the start of the underlying byte stream) if the given bytes were found.
+    ///
+    /// # Errors
+    ///
+    /// See [`seek_nth`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut seeker = AsyncByteSeeker::new(Cursor::new(b"\n\n\n\n\n")).await.unwrap();
+    /// assert_eq!(seeker.seek_nth_back(b"\n\n", 2).await.unwrap(), 1);
+    /// assert_eq!(seeker.seek_nth_back(b"\n\n", 2).await.is_err(), true);
+    /// # });
+    /// ```
+    ///
+    /// [`seek_nth`]: #method.seek_nth
+    pub async fn seek_nth_back(&mut self, bytes: &[u8], nth: usize) -> Result<u64> {
+        self.nth_seek(bytes, nth, Dir::End).await
+    }
+
+    /// Returns a stream of the offsets of the occurences of the given bytes, searching
+    /// **forwards** from where the last forward search has stopped.
+    ///
+    /// The stream ends once no more occurences are found. Errors other than
+    /// `ErrorKind::ByteNotFound` are yielded once, after which the stream ends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut seeker = AsyncByteSeeker::new(Cursor::new(b"0\n1\n2\n")).await.unwrap();
+    ///
+    /// let mut stream = seeker.find_stream(b"\n");
+    /// let mut found = Vec::new();
+    /// while let Some(pos) = stream.next().await {
+    ///     found.push(pos.unwrap());
+    /// }
+    /// assert_eq!(found, vec![1, 3, 5]);
+    /// # });
+    /// ```
+    pub fn find_stream(&mut self, bytes: &[u8]) -> FindStream<'_, R> {
+        FindStream::new(self, Finder::new(bytes), Dir::Start)
+    }
+
+    /// Returns a stream of the offsets of the occurences of the given bytes, searching
+    /// **backwards** from where the last backward search has stopped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut seeker = AsyncByteSeeker::new(Cursor::new(b"0\n1\n2\n")).await.unwrap();
+    ///
+    /// let mut stream = seeker.rfind_stream(b"\n");
+    /// assert_eq!(stream.next().await.unwrap().unwrap(), 5);
+    /// assert_eq!(stream.next().await.unwrap().unwrap(), 3);
+    /// # });
+    /// ```
+    pub fn rfind_stream(&mut self, bytes: &[u8]) -> FindStream<'_, R> {
+        FindStream::new(self, Finder::new(bytes), Dir::End)
+    }
+
+    /// Gets a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let bytes = [b'0', b'\n', b'\n', b'\n'];
+    /// let seeker = AsyncByteSeeker::new(Cursor::new(bytes)).await.unwrap();
+    /// assert_eq!(seeker.get_ref().get_ref(), &bytes);
+    /// # });
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the underlying reader
+    /// as it may corrupt this seeker's state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut seeker = AsyncByteSeeker::new(Cursor::new(b"0\n1\n")).await.unwrap();
+    /// assert_eq!(seeker.seek(b"\n").await.unwrap(), 1);
+    ///
+    /// // Moving the underlying reader does not disturb the searches.
+    /// seeker.get_mut().set_position(0);
+    /// assert_eq!(seeker.seek(b"\n").await.unwrap(), 3);
+    /// # });
+    /// ```
+    pub fn get_mut(&mut self) -> &mut R {
+        // The caller may move or modify the stream, so nothing read so far can be trusted, and
+        // neither can a seek or read that an abandoned search has left halfway.
+        self.stream_pos = None;
+        self.window.clear();
+        self.refill = None;
+        self.seeking = false;
+        &mut self.inner
+    }
+
+    /// Unwraps this `AsyncByteSeeker`, returning the underlying reader.
+    ///
+    /// The position of the underlying reader is unspecified, so seek to the desired offset before
+    /// reading from it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let bytes = [b'0', b'\n', b'\n', b'\n'];
+    /// let mut seeker = AsyncByteSeeker::new(Cursor::new(bytes)).await.unwrap();
+    /// assert_eq!(seeker.seek(b"\n").await.unwrap(), 1);
+    ///
+    /// let cursor = seeker.into_inner();
+    /// assert_eq!(cursor.into_inner(), bytes);
+    /// # });
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    async fn buf_seek(&mut self, bytes: &[u8], dir: Dir) -> Result<u64> {
+        let finder = Finder::new(bytes);
+        let mut scan = None;
+        future::poll_fn(|cx| self.poll_search(cx, &finder, dir, &mut scan)).await
+    }
+
+    async fn nth_seek(&mut self, bytes: &[u8], nth: usize, dir: Dir) -> Result<u64> {
+        let finder = Finder::new(bytes);
+        let mut counter = nth;
+        loop {
+            let mut scan = None;
+            let pos = future::poll_fn(|cx| self.poll_search(cx, &finder, dir, &mut scan)).await?;
+            match counter.checked_sub(1) {
+                None => return Err(Error::new(ErrorKind::ByteNotFound)),
+                Some(0) => return Ok(pos),
+                Some(n) => counter = n,
+            }
+        }
+    }
+
+    // Searches in the given direction between the two cursors, and advances the cursor of that
+    // direction.
+    //
+    // `scan` holds the offset the search has reached while the window is being refilled, and
+    // must be `None` when a new search starts.
+    fn poll_search(
+        &mut self,
+        cx: &mut Context<'_>,
+        finder: &Finder,
+        dir: Dir,
+        scan: &mut Option<u64>,
+    ) -> Poll<Result<u64>> {
+        let len = finder.len();
+        if len == 0 || len > self.cap {
+            return Poll::Ready(Err(Error::new(ErrorKind::UnsupportedLength)));
+        }
+
+        loop {
+            if let Err(e) = ready!(self.poll_refill(cx)) {
+                *scan = None;
+                return Poll::Ready(Err(e));
+            }
+            if scan.is_none() {
+                self.window.prepare(self.chunk_size, len, self.len);
+            }
+
+            let State { lpos, rpos } = self.state;
+            let step = match dir {
+                Dir::Start => self.window.find_forward(finder, scan.unwrap_or(lpos), rpos),
+                Dir::End => self
+                    .window
+                    .find_backward(finder, lpos, scan.unwrap_or(rpos)),
+            };
+            match step {
+                Step::Done(found) => {
+                    *scan = None;
+                    return Poll::Ready(match found {
+                        Some(m) => {
+                            self.state.advance(&m, dir, MatchMode::NonOverlapping);
+                            Ok(m.start)
+                        }
+                        None => Err(Error::new(ErrorKind::ByteNotFound)),
+                    });
+                }
+                Step::Move(pos) => {
+                    *scan = Some(pos);
+                    self.refill = Some(match dir {
+                        Dir::Start => self.window.move_forward(pos, self.len),
+                        Dir::End => self.window.move_backward(pos, 0),
+                    });
+                }
+            }
+        }
+    }
+
+    // Reads the bytes the window is waiting for, if any. The window is left empty if reading
+    // fails.
+    fn poll_refill(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let result = ready!(self.poll_read_refill(cx));
+        if result.is_err() {
+            self.refill = None;
+            self.stream_pos = None;
+            self.window.clear();
+        }
+        Poll::Ready(result)
+    }
+
+    fn poll_read_refill(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        while let Some(refill) = &mut self.refill {
+            if refill.range.is_empty() {
+                self.refill = None;
+                break;
+            }
+
+            // Seeks only if the stream is not positioned at the bytes to read already.
+            if self.stream_pos != Some(refill.pos) {
+                self.stream_pos = None;
+                let pos = SeekFrom::Start(refill.pos);
+                ready!(poll_seek(&mut self.inner, &mut self.seeking, cx, pos))?;
+                self.stream_pos = Some(refill.pos);
+            }
+
+            let mut buf = ReadBuf::new(&mut self.window.buf[refill.range.clone()]);
+            ready!(Pin::new(&mut self.inner).poll_read(cx, &mut buf))?;
+            let n = buf.filled().len();
+            if n == 0 {
+                let e = io::Error::new(io::ErrorKind::UnexpectedEof, "failed to fill whole buffer");
+                return Poll::Ready(Err(e.into()));
+            }
+            refill.pos += n as u64;
+            refill.range.start += n;
+            self.stream_pos = Some(refill.pos);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+// Seeks `stream` to `pos`, completing the seek started by an earlier call if `seeking` is set.
+fn poll_seek<R: AsyncSeek + Unpin>(
+    stream: &mut R,
+    seeking: &mut bool,
+    cx: &mut Context<'_>,
+    pos: SeekFrom,
+) -> Poll<io::Result<u64>> {
+    if !*seeking {
+        Pin::new(&mut *stream).start_seek(pos)?;
+        *seeking = true;
+    }
+    let result = ready!(Pin::new(stream).poll_complete(cx));
+    *seeking = false;
+    Poll::Ready(result)
+}
+
+/// A stream of the offsets of the occurences of a byte slice within an asynchronous stream of
+/// bytes.
+///
+/// This struct is created by [`AsyncByteSeeker::find_stream`] and
+/// [`AsyncByteSeeker::rfind_stream`]. See their documentation for more.
+///
+/// [`AsyncByteSeeker::find_stream`]: struct.AsyncByteSeeker.html#method.find_stream
+/// [`AsyncByteSeeker::rfind_stream`]: struct.AsyncByteSeeker.html#method.rfind_stream
+#[derive(Debug)]
+pub struct FindStream<'s, R: AsyncRead + AsyncSeek + Unpin> {
+    seeker: &'s mut AsyncByteSeeker<R>,
+    finder: Finder,
+    dir: Dir,
+    // The offset the pending search has reached, if any.
+    scan: Option<u64>,
+    done: bool,
+}
+
+impl<'s, R: AsyncRead + AsyncSeek + Unpin> FindStream<'s, R> {
+    fn new(seeker: &'s mut AsyncByteSeeker<R>, finder: Finder, dir: Dir) -> Self {
+        Self {
+            seeker,
+            finder,
+            dir,
+            scan: None,
+            done: false,
+        }
+    }
+
+    /// Returns the offset of the next occurence, or `None` if there are no more occurences.
+    ///
+    /// This is a shorthand for polling the `Stream` implementation, which does not need any
+    /// extension traits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::AsyncByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut seeker = AsyncByteSeeker::new(Cursor::new(b"0\n1\n2")).await.unwrap();
+    /// let mut stream = seeker.find_stream(b"\n");
+    ///
+    /// assert_eq!(stream.next().await.unwrap().unwrap(), 1);
+    /// assert_eq!(stream.next().await.unwrap().unwrap(), 3);
+    /// assert_eq!(stream.next().await.is_none(), true);
+    /// # });
+    /// ```
+    pub async fn next(&mut self) -> Option<Result<u64>> {
+        future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+}
+
+impl<'s, R: AsyncRead + AsyncSeek + Unpin> Stream for FindStream<'s, R> {
+    type Item = Result<u64>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        let result = ready!(this
+            .seeker
+            .poll_search(cx, &this.finder, this.dir, &mut this.scan));
+        match result {
+            Ok(pos) => Poll::Ready(Some(Ok(pos))),
+            Err(e) => {
+                this.done = true;
+                match e.kind() {
+                    ErrorKind::ByteNotFound => Poll::Ready(None),
+                    _ => Poll::Ready(Some(Err(e))),
+                }
+            }
+        }
+    }
+}
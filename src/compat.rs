@@ -0,0 +1,71 @@
+//! Compatibility with code written against the `usize` offsets of earlier versions.
+//!
+//! `ByteSeeker` reports offsets and lengths as `u64`, which matches `std::io::Seek` and does not
+//! truncate streams larger than 4 GiB on 32-bit targets. Importing the [`UsizeOffsets`] trait
+//! brings back `usize` flavors of the offset returning methods, which fail with
+//! `ErrorKind::OffsetOverflow` instead of silently truncating.
+//!
+//! # Examples
+//!
+//! ```
+//! use byteseeker::compat::UsizeOffsets;
+//! use byteseeker::ByteSeeker;
+//! use std::io::Cursor;
+//!
+//! let bytes = [b'0', b'\n', b'0'];
+//! let mut cursor = Cursor::new(bytes);
+//! let mut seeker = ByteSeeker::new(&mut cursor);
+//!
+//! let pos: usize = seeker.seek_usize(b"\n").unwrap();
+//! assert_eq!(pos, 1);
+//! ```
+//!
+//! [`UsizeOffsets`]: trait.UsizeOffsets.html
+use crate::error::{Error, ErrorKind, Result};
+use crate::seeker::ByteSeeker;
+use std::convert::TryFrom;
+use std::io::{Read, Seek};
+
+/// `usize` flavors of the offset returning methods of `ByteSeeker`.
+pub trait UsizeOffsets {
+    /// Like `ByteSeeker::len`, but returns a `usize`.
+    fn len_usize(&self) -> Result<usize>;
+
+    /// Like `ByteSeeker::seek`, but returns a `usize`.
+    fn seek_usize(&mut self, bytes: &[u8]) -> Result<usize>;
+
+    /// Like `ByteSeeker::seek_back`, but returns a `usize`.
+    fn seek_back_usize(&mut self, bytes: &[u8]) -> Result<usize>;
+
+    /// Like `ByteSeeker::seek_nth`, but returns a `usize`.
+    fn seek_nth_usize(&mut self, bytes: &[u8], nth: usize) -> Result<usize>;
+
+    /// Like `ByteSeeker::seek_nth_back`, but returns a `usize`.
+    fn seek_nth_back_usize(&mut self, bytes: &[u8], nth: usize) -> Result<usize>;
+}
+
+impl<R: Read + Seek> UsizeOffsets for ByteSeeker<R> {
+    fn len_usize(&self) -> Result<usize> {
+        to_usize(self.len())
+    }
+
+    fn seek_usize(&mut self, bytes: &[u8]) -> Result<usize> {
+        to_usize(self.seek(bytes)?)
+    }
+
+    fn seek_back_usize(&mut self, bytes: &[u8]) -> Result<usize> {
+        to_usize(self.seek_back(bytes)?)
+    }
+
+    fn seek_nth_usize(&mut self, bytes: &[u8], nth: usize) -> Result<usize> {
+        to_usize(self.seek_nth(bytes, nth)?)
+    }
+
+    fn seek_nth_back_usize(&mut self, bytes: &[u8], nth: usize) -> Result<usize> {
+        to_usize(self.seek_nth_back(bytes, nth)?)
+    }
+}
+
+fn to_usize(offset: u64) -> Result<usize> {
+    usize::try_from(offset).map_err(|_| Error::new(ErrorKind::OffsetOverflow(offset)))
+}
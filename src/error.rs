@@ -42,6 +42,26 @@ pub enum ErrorKind {
     /// The length of the given byte slice is zero,
     /// or excesses the capacity of `ByteSeeker`.
     UnsupportedLength,
+    /// The contained offset does not fit in a `usize` on this target.
+    ///
+    /// Can occur when using the methods of `compat::UsizeOffsets`, or when reading more bytes
+    /// into a `Vec<u8>` than can be addressed.
+    OffsetOverflow(u64),
+    /// The given hex signature is malformed at the contained offset.
+    ///
+    /// Can only occur when parsing a `MaskedPattern`.
+    InvalidPattern(usize),
+    /// The length of the underlying byte stream changed while it was being read, for example
+    /// because the file was truncated or rotated.
+    ///
+    /// Can occur when the underlying byte stream ends earlier than expected. See
+    /// `ByteSeeker::set_change_policy` for how to handle this automatically.
+    StreamChanged {
+        /// The length of the byte stream that was expected.
+        expected: u64,
+        /// The length of the byte stream that was measured after reading failed.
+        actual: u64,
+    },
 }
 
 impl fmt::Display for Error {
@@ -53,6 +73,17 @@ impl fmt::Display for Error {
                 f,
                 "The length of the given byte slice is zero, or excesses the capacity of `ByteSeeker`"
             ),
+            ErrorKind::OffsetOverflow(offset) => {
+                write!(f, "Offset {} does not fit in a `usize`", offset)
+            }
+            ErrorKind::InvalidPattern(pos) => {
+                write!(f, "Invalid hex signature at offset {}", pos)
+            }
+            ErrorKind::StreamChanged { expected, actual } => write!(
+                f,
+                "The length of the byte stream changed from {} to {}",
+                expected, actual
+            ),
         }
     }
 }
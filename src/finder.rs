@@ -0,0 +1,420 @@
+use std::fmt;
+use std::ops::Range;
+
+/// A match found within a stream of bytes.
+///
+/// This struct is returned by the searches whose matches may vary in length or in the pattern
+/// that was found, such as [`ByteSeeker::seek_any`]. Offsets are relative to the start of the
+/// underlying byte stream, and `end` is exclusive.
+///
+/// # Examples
+///
+/// ```
+/// use byteseeker::{ByteSeeker, Match};
+/// use std::io::Cursor;
+///
+/// let bytes = b"0\r\n1";
+/// let mut cursor = Cursor::new(bytes);
+/// let mut seeker = ByteSeeker::new(&mut cursor);
+///
+/// let patterns: &[&[u8]] = &[b"\n", b"\r\n"];
+/// let m = seeker.seek_any(patterns).unwrap();
+/// assert_eq!(m, Match { start: 1, end: 3, pattern_id: 1 });
+/// assert_eq!(m.range(), 1..3);
+/// assert_eq!(&bytes[m.start as usize..m.end as usize], b"\r\n");
+/// ```
+///
+/// [`ByteSeeker::seek_any`]: struct.ByteSeeker.html#method.seek_any
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Match {
+    /// The offset of the first byte of the match.
+    pub start: u64,
+    /// The offset right after the last byte of the match.
+    pub end: u64,
+    /// The index of the found pattern, or `0` if only one pattern was searched for.
+    pub pattern_id: usize,
+}
+
+impl Match {
+    /// Returns the range of offsets of this match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::Match;
+    ///
+    /// let m = Match { start: 2, end: 5, pattern_id: 0 };
+    /// assert_eq!(m.range(), 2..5);
+    /// ```
+    pub fn range(&self) -> Range<u64> {
+        self.start..self.end
+    }
+
+    /// Returns the length of this match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::Match;
+    ///
+    /// let m = Match { start: 2, end: 5, pattern_id: 0 };
+    /// assert_eq!(m.len(), 3);
+    /// ```
+    pub fn len(&self) -> u64 {
+        self.end - self.start
+    }
+
+    /// Returns `true` if this match is empty.
+    ///
+    /// Searches never return empty matches, but a `Match` can be built by hand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::Match;
+    ///
+    /// assert_eq!(Match { start: 2, end: 2, pattern_id: 0 }.is_empty(), true);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Shifts this match by the given offset.
+    pub(crate) fn offset(self, offset: u64) -> Self {
+        Self {
+            start: self.start + offset,
+            end: self.end + offset,
+            ..self
+        }
+    }
+}
+
+impl From<Match> for Range<u64> {
+    fn from(m: Match) -> Range<u64> {
+        m.range()
+    }
+}
+
+/// Something that can be searched for within in-memory byte slices, in both directions.
+///
+/// `ByteSeeker` reads the underlying stream chunk by chunk, and relies on `max_len` to decide how
+/// many bytes must be carried over between chunks.
+pub(crate) trait Search {
+    /// Returns the minimum length of a match.
+    fn min_len(&self) -> usize;
+
+    /// Returns the maximum length of a match.
+    fn max_len(&self) -> usize;
+
+    /// Returns the match that starts first within `haystack`.
+    fn find_in(&self, haystack: &[u8]) -> Option<Match>;
+
+    /// Returns the match that ends last within `haystack`.
+    fn rfind_in(&self, haystack: &[u8]) -> Option<Match>;
+}
+
+/// A byte slice prepared for searching, which can be reused across searches and `ByteSeeker`s.
+///
+/// `ByteSeeker::seek` prepares the given byte slice on every call. If the same byte slice is
+/// sought many times, prepare it once with `Finder::new` and pass it to
+/// [`ByteSeeker::seek_with`] or [`ByteSeeker::seek_back_with`] instead.
+///
+/// The search is a Boyer-Moore-Horspool variant: the byte aligned with the end of the needle (or
+/// the start of the needle, when searching backwards) determines how far the needle can be
+/// shifted after a mismatch, so most bytes of the haystack are never inspected.
+///
+/// # Examples
+///
+/// ```
+/// use byteseeker::{ByteSeeker, Finder};
+/// use std::io::Cursor;
+///
+/// let finder = Finder::new(b"\r\n");
+///
+/// for &(bytes, pos) in &[("0\r\n1\r\n", 1), ("\r\n", 0)] {
+///     let mut cursor = Cursor::new(bytes.as_bytes());
+///     let mut seeker = ByteSeeker::new(&mut cursor);
+///     assert_eq!(seeker.seek_with(&finder).unwrap(), pos);
+/// }
+/// ```
+///
+/// [`ByteSeeker::seek_with`]: struct.ByteSeeker.html#method.seek_with
+/// [`ByteSeeker::seek_back_with`]: struct.ByteSeeker.html#method.seek_back_with
+#[derive(Clone)]
+pub struct Finder {
+    needle: Vec<u8>,
+    fwd: [usize; 256],
+    bwd: [usize; 256],
+    ignore_case: bool,
+}
+
+// The shift tables are of no use when debugging, and would bury the needle.
+impl fmt::Debug for Finder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Finder")
+            .field("needle", &self.needle)
+            .finish()
+    }
+}
+
+impl Finder {
+    /// Prepares the given byte slice for searching, in both directions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::Finder;
+    ///
+    /// let finder = Finder::new(b"\n");
+    /// ```
+    pub fn new(needle: &[u8]) -> Self {
+        Finder::with_case(needle, false)
+    }
+
+    /// Prepares the given byte slice for searching, in both directions, ignoring the case of
+    /// ASCII letters.
+    ///
+    /// Only the needle is case-folded: the haystack is compared byte by byte, so searching does
+    /// not need a lowercased copy of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::{ByteSeeker, Finder};
+    /// use std::io::Cursor;
+    ///
+    /// let finder = Finder::new_ascii_case_insensitive(b"content-type:");
+    ///
+    /// let bytes = b"Host: x\r\nContent-Type: text/plain\r\n";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    /// assert_eq!(seeker.seek_with(&finder).unwrap(), 9);
+    /// ```
+    pub fn new_ascii_case_insensitive(needle: &[u8]) -> Self {
+        Finder::with_case(needle, true)
+    }
+
+    fn with_case(needle: &[u8], ignore_case: bool) -> Self {
+        let len = needle.len();
+        let mut fwd = [len; 256];
+        let mut bwd = [len; 256];
+
+        // Either case of a letter shifts the needle as far as the letter itself.
+        let cases = |b: u8| {
+            if ignore_case {
+                [b.to_ascii_lowercase(), b.to_ascii_uppercase()]
+            } else {
+                [b, b]
+            }
+        };
+        if len > 0 {
+            for (i, &b) in needle[..len - 1].iter().enumerate() {
+                for &b in &cases(b) {
+                    fwd[b as usize] = len - 1 - i;
+                }
+            }
+            for (i, &b) in needle.iter().enumerate().skip(1).rev() {
+                for &b in &cases(b) {
+                    bwd[b as usize] = i;
+                }
+            }
+        }
+
+        Self {
+            needle: needle.to_vec(),
+            fwd,
+            bwd,
+            ignore_case,
+        }
+    }
+
+    /// Returns the byte slice this `Finder` searches for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::Finder;
+    ///
+    /// let finder = Finder::new(b"\n");
+    /// assert_eq!(finder.needle(), b"\n");
+    /// ```
+    pub fn needle(&self) -> &[u8] {
+        &self.needle
+    }
+
+    /// Returns `true` if this `Finder` ignores the case of ASCII letters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::Finder;
+    ///
+    /// assert_eq!(Finder::new(b"a").is_ascii_case_insensitive(), false);
+    /// assert_eq!(Finder::new_ascii_case_insensitive(b"a").is_ascii_case_insensitive(), true);
+    /// ```
+    pub fn is_ascii_case_insensitive(&self) -> bool {
+        self.ignore_case
+    }
+
+    /// Returns `true` if the given haystack byte matches the given needle byte.
+    pub(crate) fn eq_byte(&self, x: u8, b: u8) -> bool {
+        if self.ignore_case {
+            x.eq_ignore_ascii_case(&b)
+        } else {
+            x == b
+        }
+    }
+
+    // Returns `true` if the given part of the haystack matches the given part of the needle.
+    fn eq_slice(&self, haystack: &[u8], needle: &[u8]) -> bool {
+        if self.ignore_case {
+            haystack.eq_ignore_ascii_case(needle)
+        } else {
+            haystack == needle
+        }
+    }
+
+    /// Returns the length of the needle.
+    pub(crate) fn len(&self) -> usize {
+        self.needle.len()
+    }
+
+    /// Returns the offset of the first occurence of the needle within `haystack`.
+    pub(crate) fn find(&self, haystack: &[u8]) -> Option<usize> {
+        let needle = &self.needle[..];
+        let len = needle.len();
+        if len == 0 || haystack.len() < len {
+            return None;
+        }
+        if len == 1 {
+            return haystack.iter().position(|&x| self.eq_byte(x, needle[0]));
+        }
+
+        let last = len - 1;
+        let mut i = 0;
+        while i + len <= haystack.len() {
+            let b = haystack[i + last];
+            if self.eq_byte(b, needle[last])
+                && self.eq_slice(&haystack[i..i + last], &needle[..last])
+            {
+                return Some(i);
+            }
+            i += self.fwd[b as usize];
+        }
+        None
+    }
+
+    /// Returns the offset of the last occurence of the needle within `haystack`.
+    pub(crate) fn rfind(&self, haystack: &[u8]) -> Option<usize> {
+        let needle = &self.needle[..];
+        let len = needle.len();
+        if len == 0 || haystack.len() < len {
+            return None;
+        }
+        if len == 1 {
+            return haystack.iter().rposition(|&x| self.eq_byte(x, needle[0]));
+        }
+
+        let mut i = haystack.len() - len;
+        loop {
+            let b = haystack[i];
+            if self.eq_byte(b, needle[0]) && self.eq_slice(&haystack[i + 1..i + len], &needle[1..])
+            {
+                return Some(i);
+            }
+            let shift = self.bwd[b as usize];
+            if i < shift {
+                return None;
+            }
+            i -= shift;
+        }
+    }
+}
+
+impl Search for Finder {
+    fn min_len(&self) -> usize {
+        self.len()
+    }
+
+    fn max_len(&self) -> usize {
+        self.len()
+    }
+
+    fn find_in(&self, haystack: &[u8]) -> Option<Match> {
+        self.find(haystack).map(|start| Match {
+            start: start as u64,
+            end: (start + self.len()) as u64,
+            pattern_id: 0,
+        })
+    }
+
+    fn rfind_in(&self, haystack: &[u8]) -> Option<Match> {
+        self.rfind(haystack).map(|start| Match {
+            start: start as u64,
+            end: (start + self.len()) as u64,
+            pattern_id: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        let finder = Finder::new(b"\n2024-");
+        assert_eq!(finder.find(b""), None);
+        assert_eq!(finder.find(b"\n2024"), None);
+        assert_eq!(finder.find(b"\n2024-"), Some(0));
+        assert_eq!(finder.find(b"\n\n\n2023-\n2024-\n2024-"), Some(8));
+
+        let finder = Finder::new(b"aab");
+        assert_eq!(finder.find(b"aaaaaab"), Some(4));
+        assert_eq!(finder.find(b"aaaaaaa"), None);
+
+        let finder = Finder::new(b"\n");
+        assert_eq!(finder.find(b"0\n\n"), Some(1));
+    }
+
+    #[test]
+    fn test_rfind() {
+        let finder = Finder::new(b"\n2024-");
+        assert_eq!(finder.rfind(b""), None);
+        assert_eq!(finder.rfind(b"\n2024"), None);
+        assert_eq!(finder.rfind(b"\n2024-"), Some(0));
+        assert_eq!(finder.rfind(b"\n2024-\n2024-\n2023-\n\n"), Some(6));
+
+        let finder = Finder::new(b"baa");
+        assert_eq!(finder.rfind(b"baaaaaa"), Some(0));
+        assert_eq!(finder.rfind(b"aaaaaaa"), None);
+
+        let finder = Finder::new(b"\n");
+        assert_eq!(finder.rfind(b"\n\n0"), Some(1));
+    }
+
+    #[test]
+    fn test_find_ascii_case_insensitive() {
+        let finder = Finder::new_ascii_case_insensitive(b"Content-Type:");
+        assert_eq!(finder.find(b"content-type"), None);
+        assert_eq!(finder.find(b"x\r\nCONTENT-TYPE: a"), Some(3));
+        assert_eq!(
+            finder.rfind(b"content-type: a\r\nContent-type: b"),
+            Some(17)
+        );
+
+        let finder = Finder::new_ascii_case_insensitive(b"aB");
+        assert_eq!(finder.find(b"AAAAAb"), Some(4));
+        assert_eq!(finder.rfind(b"abAAAA"), Some(0));
+
+        let finder = Finder::new_ascii_case_insensitive(b"x");
+        assert_eq!(finder.find(b"0X0x"), Some(1));
+        assert_eq!(finder.rfind(b"0X0x"), Some(3));
+    }
+
+    #[test]
+    fn test_debug() {
+        let finder = Finder::new(b"ab");
+        assert_eq!(format!("{:?}", finder), "Finder { needle: [97, 98] }");
+    }
+}
@@ -0,0 +1,206 @@
+use crate::error::{ErrorKind, Result};
+use crate::finder::Finder;
+use crate::seeker::{ByteSeeker, Dir, MatchMode};
+use std::io::{Read, Seek};
+use std::iter::FusedIterator;
+
+/// An iterator over the offsets of the occurences of a byte slice within a stream of bytes.
+///
+/// This struct is created by [`ByteSeeker::find_iter`] and [`ByteSeeker::rfind_iter`]. See their
+/// documentation for more.
+///
+/// [`ByteSeeker::find_iter`]: struct.ByteSeeker.html#method.find_iter
+/// [`ByteSeeker::rfind_iter`]: struct.ByteSeeker.html#method.rfind_iter
+#[derive(Debug)]
+pub struct FindIter<'s, R: Read + Seek> {
+    seeker: &'s mut ByteSeeker<R>,
+    finder: Finder,
+    done: bool,
+}
+
+impl<'s, R: Read + Seek> FindIter<'s, R> {
+    pub(crate) fn new(seeker: &'s mut ByteSeeker<R>, finder: Finder) -> Self {
+        Self {
+            seeker,
+            finder,
+            done: false,
+        }
+    }
+
+    fn next_in(&mut self, dir: Dir) -> Option<Result<u64>> {
+        if self.done {
+            return None;
+        }
+
+        let mode = self.seeker.match_mode();
+        match self.seeker.finder_seek(&self.finder, dir, mode) {
+            Ok(pos) => Some(Ok(pos)),
+            Err(e) => {
+                self.done = true;
+                match e.kind() {
+                    ErrorKind::ByteNotFound => None,
+                    _ => Some(Err(e)),
+                }
+            }
+        }
+    }
+}
+
+impl<'s, R: Read + Seek> Iterator for FindIter<'s, R> {
+    type Item = Result<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_in(Dir::Start)
+    }
+}
+
+impl<'s, R: Read + Seek> DoubleEndedIterator for FindIter<'s, R> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next_in(Dir::End)
+    }
+}
+
+impl<'s, R: Read + Seek> FusedIterator for FindIter<'s, R> {}
+
+/// An iterator over the records of a stream of bytes separated by a delimiter, yielding the
+/// offset and the contents of each record.
+///
+/// This struct is created by [`ByteSeeker::split`] and [`ByteSeeker::rsplit`]. See their
+/// documentation for more.
+///
+/// [`ByteSeeker::split`]: struct.ByteSeeker.html#method.split
+/// [`ByteSeeker::rsplit`]: struct.ByteSeeker.html#method.rsplit
+#[derive(Debug)]
+pub struct Split<'s, R: Read + Seek> {
+    seeker: &'s mut ByteSeeker<R>,
+    finder: Finder,
+    dir: Dir,
+    started: bool,
+    done: bool,
+}
+
+impl<'s, R: Read + Seek> Split<'s, R> {
+    pub(crate) fn new(seeker: &'s mut ByteSeeker<R>, finder: Finder, dir: Dir) -> Self {
+        Self {
+            seeker,
+            finder,
+            dir,
+            started: false,
+            done: false,
+        }
+    }
+
+    fn next_record(&mut self) -> Result<Option<(u64, Vec<u8>)>> {
+        let (start, end) = (self.seeker.position(), self.seeker.back_position());
+        // Backwards, a delimiter at the very start is preceded by an empty record.
+        if start >= end && (!self.started || matches!(self.dir, Dir::Start)) {
+            return Ok(None);
+        }
+
+        if !self.started {
+            self.started = true;
+            // A delimiter at the very end terminates the last record rather than starting an
+            // empty one after it.
+            if let Dir::End = self.dir {
+                match self
+                    .seeker
+                    .finder_seek(&self.finder, Dir::End, MatchMode::NonOverlapping)
+                {
+                    Ok(pos) if pos + self.finder.len() as u64 == end => {}
+                    Ok(_) => self.seeker.set_back_position(end),
+                    Err(e) => match e.kind() {
+                        ErrorKind::ByteNotFound => {}
+                        _ => return Err(e),
+                    },
+                }
+            }
+        }
+
+        let mut record = Vec::new();
+        match self.seeker.finder_read(&self.finder, self.dir, &mut record) {
+            Ok(pos) => match self.dir {
+                Dir::Start => Ok(Some((start, record))),
+                Dir::End => Ok(Some((pos + self.finder.len() as u64, record))),
+            },
+            Err(e) => match e.kind() {
+                ErrorKind::ByteNotFound => {
+                    // The record at the far end, which has no delimiter after (or before) it.
+                    let end = self.seeker.back_position();
+                    self.done = true;
+                    self.seeker.read_into(start..end, &mut record)?;
+                    match self.dir {
+                        Dir::Start => self.seeker.set_position(end),
+                        Dir::End => self.seeker.set_back_position(start),
+                    }
+                    Ok(Some((start, record)))
+                }
+                _ => Err(e),
+            },
+        }
+    }
+}
+
+impl<'s, R: Read + Seek> Iterator for Split<'s, R> {
+    type Item = Result<(u64, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.next_record() {
+            Ok(Some(record)) => Some(Ok(record)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl<'s, R: Read + Seek> FusedIterator for Split<'s, R> {}
+
+/// A reverse iterator over the lines of a stream of bytes, yielding the offset and the contents
+/// of each line.
+///
+/// This struct is created by [`ByteSeeker::lines_back`]. See its documentation for more.
+///
+/// [`ByteSeeker::lines_back`]: struct.ByteSeeker.html#method.lines_back
+#[derive(Debug)]
+pub struct LinesBack<'s, R: Read + Seek> {
+    split: Split<'s, R>,
+    end: u64,
+}
+
+impl<'s, R: Read + Seek> LinesBack<'s, R> {
+    pub(crate) fn new(seeker: &'s mut ByteSeeker<R>) -> Self {
+        let end = seeker.back_position();
+        Self {
+            split: Split::new(seeker, Finder::new(b"\n"), Dir::End),
+            end,
+        }
+    }
+}
+
+impl<'s, R: Read + Seek> Iterator for LinesBack<'s, R> {
+    type Item = Result<(u64, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.end;
+        self.split.next().map(|line| {
+            line.map(|(pos, mut line)| {
+                // Only a line followed by `\n` can end with `\r\n`.
+                if pos + (line.len() as u64) < end && line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+                (pos, line)
+            })
+        })
+    }
+}
+
+impl<'s, R: Read + Seek> FusedIterator for LinesBack<'s, R> {}
@@ -10,44 +10,72 @@
 //!
 //! ```no_run
 //! use byteseeker::{ByteSeeker, Result};
-//! use std::io::{Read, Seek, SeekFrom};
 //! use std::fs::File;
 //!
-//! fn read_last_line(f: &mut File, buf: &mut Vec<u8>) -> Result<()> {
+//! fn read_last_line(f: &mut File) -> Result<Vec<u8>> {
 //!     let mut seeker = ByteSeeker::new(f);
-//!     let pos = seeker.seek_back(b"\n")?;
-//!     let starting = if pos == seeker.len() - 1 {
-//!         // if file ends with a newline.
-//!         seeker.seek_back(b"\n")? + 1
-//!     } else {
-//!         // if file doesn't end with a newline.
-//!         pos + 1
-//!     };
-//!
-//!     f.seek(SeekFrom::Start(starting as u64))?;
-//!     f.read_to_end(buf)?;
-//!     Ok(())
+//!     // Handles both `\n` and `\r\n`, with or without a final newline.
+//!     let last = seeker.tail(1)?.pop();
+//!     Ok(last.map(|(_, line)| line).unwrap_or_default())
 //! }
 //!
 //! fn main() -> Result<()> {
 //!    let mut f = File::open("./data.csv")?;
-//!    let mut buf = Vec::new();
-//!    read_last_line(&mut f, &mut buf)?;
+//!    let line = read_last_line(&mut f)?;
 //!
 //!    // For simplicity, we just assume the given file is UTF-8 valid and unwrap the result here.
-//!    println!("{}", std::str::from_utf8(&buf).unwrap());
+//!    println!("{}", std::str::from_utf8(&line).unwrap());
 //!
 //!    Ok(())
 //! }
 //!
 //! ```
 //!
+//! # Crate features
+//!
+//! - `regex`: enables [`ByteSeeker::seek_regex`] and [`ByteSeeker::seek_regex_back`], which
+//!   search for matches of a [`regex::bytes::Regex`].
+//! - `tokio`: enables [`AsyncByteSeeker`], which seeks within byte streams that implement tokio's
+//!   `AsyncRead` and `AsyncSeek`.
+//!
+//! # Minimum supported Rust version
+//!
+//! This crate, along with all of its features, builds with Rust 1.70 or later.
+//!
+//! [`AsyncByteSeeker`]: struct.AsyncByteSeeker.html
 //! [`ByteSeeker`]: struct.ByteSeeker.html
+//! [`ByteSeeker::seek_regex`]: struct.ByteSeeker.html#method.seek_regex
+//! [`ByteSeeker::seek_regex_back`]: struct.ByteSeeker.html#method.seek_regex_back
+//! [`regex::bytes::Regex`]: https://docs.rs/regex/1/regex/bytes/struct.Regex.html
 //! [byteslice]: https://doc.rust-lang.org/std/primitive.slice.html
 #![deny(missing_docs)]
+#![forbid(unsafe_code)]
+
+#[cfg(feature = "tokio")]
+mod async_seeker;
+#[cfg(feature = "tokio")]
+pub use async_seeker::{AsyncByteSeeker, FindStream};
+
+pub mod compat;
 
 mod error;
 pub use error::{Error, ErrorKind, Result};
 
+mod finder;
+pub use finder::{Finder, Match};
+
+mod iter;
+pub use iter::{FindIter, LinesBack, Split};
+
+mod masked;
+pub use masked::MaskedPattern;
+
+mod multi;
+
+#[cfg(feature = "regex")]
+mod re;
+
 mod seeker;
-pub use seeker::ByteSeeker;
+pub use seeker::{ByteSeeker, ChangePolicy, MatchMode};
+
+mod window;
@@ -0,0 +1,263 @@
+use crate::error::{Error, ErrorKind, Result};
+use crate::finder::{Match, Search};
+use std::str::FromStr;
+
+/// A byte pattern with "don't care" bits, such as the signature of a binary file format.
+///
+/// A byte of the haystack matches a byte of the pattern if they are equal after applying the mask
+/// of that byte, so a mask of `0xff` requires an exact match and a mask of `0x00` matches any
+/// byte. Signatures written in hex, like `"50 4B ?? ?? 14 00"`, can be parsed using
+/// [`MaskedPattern::from_hex`].
+///
+/// # Examples
+///
+/// ```
+/// use byteseeker::{ByteSeeker, MaskedPattern};
+/// use std::io::Cursor;
+///
+/// let pattern = MaskedPattern::new(b"PK\0\0\x14\0", &[0xff, 0xff, 0, 0, 0xff, 0xff]);
+///
+/// let bytes = b"\0\0PK\x03\x04\x14\0";
+/// let mut cursor = Cursor::new(bytes);
+/// let mut seeker = ByteSeeker::new(&mut cursor);
+/// assert_eq!(seeker.seek_masked(&pattern).unwrap(), 2);
+/// ```
+///
+/// [`MaskedPattern::from_hex`]: #method.from_hex
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MaskedPattern {
+    bytes: Vec<u8>,
+    mask: Vec<u8>,
+    // The index of a byte that must match exactly, used to skip ahead quickly.
+    anchor: Option<usize>,
+}
+
+impl MaskedPattern {
+    /// Creates a pattern from its bytes and the mask of each byte.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` and `mask` differ in length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::MaskedPattern;
+    ///
+    /// let pattern = MaskedPattern::new(b"\x50\x4b\x00", &[0xff, 0xff, 0x00]);
+    /// assert_eq!(pattern.len(), 3);
+    /// ```
+    pub fn new(bytes: &[u8], mask: &[u8]) -> Self {
+        assert_eq!(
+            bytes.len(),
+            mask.len(),
+            "the bytes and the mask of a pattern must have the same length"
+        );
+
+        Self {
+            bytes: bytes.iter().zip(mask).map(|(&b, &m)| b & m).collect(),
+            mask: mask.to_vec(),
+            anchor: mask.iter().position(|&m| m == 0xff),
+        }
+    }
+
+    /// Parses a pattern from a hex signature, such as `"50 4B ?? ?? 14 00"`.
+    ///
+    /// Each byte is written as two hex digits, either of which can be `?` to match any value of
+    /// that nibble. Whitespace between bytes is ignored.
+    ///
+    /// # Errors
+    ///
+    /// If the signature is malformed, an error variant of `ErrorKind::InvalidPattern` will be
+    /// returned, holding the offset of the first invalid character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::MaskedPattern;
+    ///
+    /// let pattern = MaskedPattern::from_hex("50 4B ?? ?? 14 00").unwrap();
+    /// assert_eq!(
+    ///     pattern,
+    ///     MaskedPattern::new(b"PK\0\0\x14\0", &[0xff, 0xff, 0, 0, 0xff, 0xff])
+    /// );
+    ///
+    /// assert_eq!(MaskedPattern::from_hex("50 4").is_err(), true);
+    /// ```
+    pub fn from_hex(signature: &str) -> Result<Self> {
+        let mut bytes = Vec::new();
+        let mut mask = Vec::new();
+        let mut high: Option<(u8, u8)> = None;
+
+        for (i, c) in signature.char_indices() {
+            if c.is_whitespace() {
+                if high.is_some() {
+                    return Err(Error::new(ErrorKind::InvalidPattern(i)));
+                }
+                continue;
+            }
+
+            let (nibble, nibble_mask) = match c {
+                '?' => (0, 0),
+                _ => match c.to_digit(16) {
+                    Some(d) => (d as u8, 0xf),
+                    None => return Err(Error::new(ErrorKind::InvalidPattern(i))),
+                },
+            };
+            match high.take() {
+                None => high = Some((nibble, nibble_mask)),
+                Some((b, m)) => {
+                    bytes.push(b << 4 | nibble);
+                    mask.push(m << 4 | nibble_mask);
+                }
+            }
+        }
+        if high.is_some() {
+            return Err(Error::new(ErrorKind::InvalidPattern(signature.len())));
+        }
+
+        Ok(MaskedPattern::new(&bytes, &mask))
+    }
+
+    /// Returns the length of this pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::MaskedPattern;
+    ///
+    /// let pattern = MaskedPattern::from_hex("50 4B ?? ??").unwrap();
+    /// assert_eq!(pattern.len(), 4);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns `true` if this pattern is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::MaskedPattern;
+    ///
+    /// assert_eq!(MaskedPattern::from_hex("").unwrap().is_empty(), true);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    // Returns `true` if the pattern matches `haystack` at `i`.
+    fn is_match_at(&self, haystack: &[u8], i: usize) -> bool {
+        haystack[i..i + self.len()]
+            .iter()
+            .zip(self.bytes.iter().zip(&self.mask))
+            .all(|(&x, (&b, &m))| x & m == b)
+    }
+
+    // Returns the number of offsets within `haystack` the pattern may start at, if any.
+    fn candidates(&self, haystack: &[u8]) -> Option<usize> {
+        if self.is_empty() || haystack.len() < self.len() {
+            return None;
+        }
+        Some(haystack.len() - self.len() + 1)
+    }
+
+    /// Returns the offset of the first match of the pattern within `haystack`.
+    pub(crate) fn find(&self, haystack: &[u8]) -> Option<usize> {
+        let n = self.candidates(haystack)?;
+        match self.anchor {
+            // Only offsets where the anchor byte matches are worth checking.
+            Some(a) => haystack[a..a + n]
+                .iter()
+                .enumerate()
+                .filter(|&(_, &x)| x == self.bytes[a])
+                .map(|(i, _)| i)
+                .find(|&i| self.is_match_at(haystack, i)),
+            None => (0..n).find(|&i| self.is_match_at(haystack, i)),
+        }
+    }
+
+    /// Returns the offset of the last match of the pattern within `haystack`.
+    pub(crate) fn rfind(&self, haystack: &[u8]) -> Option<usize> {
+        let n = self.candidates(haystack)?;
+        match self.anchor {
+            Some(a) => haystack[a..a + n]
+                .iter()
+                .enumerate()
+                .rev()
+                .filter(|&(_, &x)| x == self.bytes[a])
+                .map(|(i, _)| i)
+                .find(|&i| self.is_match_at(haystack, i)),
+            None => (0..n).rev().find(|&i| self.is_match_at(haystack, i)),
+        }
+    }
+}
+
+impl FromStr for MaskedPattern {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        MaskedPattern::from_hex(s)
+    }
+}
+
+impl Search for MaskedPattern {
+    fn min_len(&self) -> usize {
+        self.len()
+    }
+
+    fn max_len(&self) -> usize {
+        self.len()
+    }
+
+    fn find_in(&self, haystack: &[u8]) -> Option<Match> {
+        self.find(haystack).map(|start| Match {
+            start: start as u64,
+            end: (start + self.len()) as u64,
+            pattern_id: 0,
+        })
+    }
+
+    fn rfind_in(&self, haystack: &[u8]) -> Option<Match> {
+        self.rfind(haystack).map(|start| Match {
+            start: start as u64,
+            end: (start + self.len()) as u64,
+            pattern_id: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_hex() {
+        let pattern = MaskedPattern::from_hex("50 4b?? 1? ?4").unwrap();
+        assert_eq!(pattern.bytes, vec![0x50, 0x4b, 0x00, 0x10, 0x04]);
+        assert_eq!(pattern.mask, vec![0xff, 0xff, 0x00, 0xf0, 0x0f]);
+        assert_eq!(pattern.anchor, Some(0));
+
+        for &(signature, pos) in &[("5", 1), ("50 4 B", 4), ("5g", 1), ("50 4B ?", 7)] {
+            match MaskedPattern::from_hex(signature).unwrap_err().kind() {
+                ErrorKind::InvalidPattern(i) => assert_eq!(*i, pos),
+                _ => panic!("unexpected error"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        let pattern = MaskedPattern::from_hex("50 4B ?? ?? 14 00").unwrap();
+        assert_eq!(pattern.find(b""), None);
+        assert_eq!(pattern.find(b"PK\x03\x04\x14"), None);
+        assert_eq!(pattern.find(b"PPK\x03\x04\x14\0PK\x01\x02\x14\0"), Some(1));
+        assert_eq!(pattern.rfind(b"PPK\x03\x04\x14\0PK\x01\x02\x14\0"), Some(7));
+
+        let pattern = MaskedPattern::from_hex("?? ?1").unwrap();
+        assert_eq!(pattern.anchor, None);
+        assert_eq!(pattern.find(b"\x01\x01\x11"), Some(0));
+        assert_eq!(pattern.rfind(b"\x01\x01\x11"), Some(1));
+        assert_eq!(pattern.find(b"\x01\x02"), None);
+    }
+}
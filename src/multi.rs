@@ -0,0 +1,304 @@
+use crate::finder::{Match, Search};
+use std::collections::VecDeque;
+
+/// Several patterns prepared for searching within in-memory byte slices at once.
+///
+/// Forward searches run an Aho-Corasick automaton built from the patterns, while backward
+/// searches run another one built from the reversed patterns over the haystack in reverse.
+///
+/// Among overlapping matches, the one that starts first (or ends last, when searching backwards)
+/// wins, and ties are broken in favor of the pattern that comes first.
+///
+/// When ignoring the case of ASCII letters, the automata are built from the lowercased patterns
+/// and fed with the lowercased haystack, one byte at a time.
+#[derive(Clone, Debug)]
+pub(crate) struct MultiFinder {
+    patterns: Vec<Vec<u8>>,
+    ignore_case: bool,
+    min_len: usize,
+    max_len: usize,
+    fwd: Automaton,
+    bwd: Automaton,
+}
+
+impl MultiFinder {
+    /// Builds the automata of the given patterns.
+    pub(crate) fn new(patterns: &[&[u8]], ignore_case: bool) -> Self {
+        let patterns: Vec<Vec<u8>> = patterns.iter().map(|pattern| pattern.to_vec()).collect();
+        let folded: Vec<Vec<u8>> = if ignore_case {
+            patterns
+                .iter()
+                .map(|pattern| pattern.to_ascii_lowercase())
+                .collect()
+        } else {
+            patterns.clone()
+        };
+        let reversed: Vec<Vec<u8>> = folded
+            .iter()
+            .map(|pattern| pattern.iter().rev().cloned().collect())
+            .collect();
+
+        Self {
+            min_len: patterns.iter().map(Vec::len).min().unwrap_or(0),
+            max_len: patterns.iter().map(Vec::len).max().unwrap_or(0),
+            fwd: Automaton::new(&folded),
+            bwd: Automaton::new(&reversed),
+            patterns,
+            ignore_case,
+        }
+    }
+
+    /// Returns `true` if this `MultiFinder` was built from the given patterns and options.
+    pub(crate) fn is_built_from(&self, patterns: &[&[u8]], ignore_case: bool) -> bool {
+        self.ignore_case == ignore_case
+            && self.patterns.len() == patterns.len()
+            && self.patterns.iter().zip(patterns).all(|(a, b)| a == b)
+    }
+
+    // Returns the given haystack byte as the automata expect it.
+    fn fold(&self, b: u8) -> u8 {
+        if self.ignore_case {
+            b.to_ascii_lowercase()
+        } else {
+            b
+        }
+    }
+}
+
+impl Search for MultiFinder {
+    fn min_len(&self) -> usize {
+        self.min_len
+    }
+
+    fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    fn find_in(&self, haystack: &[u8]) -> Option<Match> {
+        let mut best: Option<Match> = None;
+        let mut state = 0;
+        for (i, &b) in haystack.iter().enumerate() {
+            // Matches ending from here on cannot start at or before the best one.
+            if let Some(m) = best {
+                if i as u64 >= m.start + self.max_len as u64 {
+                    break;
+                }
+            }
+
+            state = self.fwd.next(state, self.fold(b));
+            for &pattern in self.fwd.matches(state) {
+                let m = Match {
+                    start: (i + 1 - self.patterns[pattern].len()) as u64,
+                    end: (i + 1) as u64,
+                    pattern_id: pattern,
+                };
+                best = match best {
+                    Some(x) if (x.start, x.pattern_id) < (m.start, m.pattern_id) => Some(x),
+                    _ => Some(m),
+                };
+            }
+        }
+        best
+    }
+
+    fn rfind_in(&self, haystack: &[u8]) -> Option<Match> {
+        let mut best: Option<Match> = None;
+        let mut state = 0;
+        for (i, &b) in haystack.iter().enumerate().rev() {
+            // Matches starting from here on cannot end at or after the best one.
+            if let Some(m) = best {
+                if ((i + self.max_len) as u64) < m.end {
+                    break;
+                }
+            }
+
+            state = self.bwd.next(state, self.fold(b));
+            for &pattern in self.bwd.matches(state) {
+                let m = Match {
+                    start: i as u64,
+                    end: (i + self.patterns[pattern].len()) as u64,
+                    pattern_id: pattern,
+                };
+                best = match best {
+                    Some(x) if (x.end, m.pattern_id) > (m.end, x.pattern_id) => Some(x),
+                    _ => Some(m),
+                };
+            }
+        }
+        best
+    }
+}
+
+/// A deterministic Aho-Corasick automaton, whose state `0` is the initial state.
+#[derive(Clone, Debug)]
+struct Automaton {
+    // The transitions of state `s` are stored at `trans[s * 256..(s + 1) * 256]`.
+    trans: Vec<usize>,
+    // The indices of the patterns that end when entering each state.
+    out: Vec<Vec<usize>>,
+}
+
+impl Automaton {
+    fn new(patterns: &[Vec<u8>]) -> Self {
+        // Builds the trie of the patterns, with `0` meaning "no transition" for now, which is
+        // fine as no transition leads back to the root in a trie.
+        let mut trans = vec![0; 256];
+        let mut out = vec![Vec::new()];
+        for (index, pattern) in patterns.iter().enumerate() {
+            let mut state = 0;
+            for &b in pattern {
+                let next = trans[state * 256 + b as usize];
+                state = if next == 0 {
+                    let next = out.len();
+                    trans[state * 256 + b as usize] = next;
+                    trans.extend_from_slice(&[0; 256]);
+                    out.push(Vec::new());
+                    next
+                } else {
+                    next
+                };
+            }
+            out[state].push(index);
+        }
+
+        // Computes the failure links breadth-first, and completes the missing transitions with
+        // the transitions of the failure state, so searching never needs to follow them.
+        let mut fail = vec![0; out.len()];
+        let mut queue = VecDeque::new();
+        queue.extend(trans[..256].iter().cloned().filter(|&next| next != 0));
+        while let Some(state) = queue.pop_front() {
+            let inherited = out[fail[state]].clone();
+            out[state].extend(inherited);
+            for b in 0..256 {
+                let next = trans[state * 256 + b];
+                let fallback = trans[fail[state] * 256 + b];
+                if next == 0 {
+                    trans[state * 256 + b] = fallback;
+                } else {
+                    fail[next] = fallback;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        Self { trans, out }
+    }
+
+    fn next(&self, state: usize, b: u8) -> usize {
+        self.trans[state * 256 + b as usize]
+    }
+
+    fn matches(&self, state: usize) -> &[usize] {
+        &self.out[state]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_in() {
+        let finder = MultiFinder::new(&[b"\r\n", b"\n", b"\x1e"], false);
+        assert_eq!(finder.find_in(b""), None);
+        assert_eq!(finder.find_in(b"0\r0"), None);
+        assert_eq!(
+            finder.find_in(b"0\r\n0\n"),
+            Some(Match {
+                start: 1,
+                end: 3,
+                pattern_id: 0
+            })
+        );
+        assert_eq!(
+            finder.find_in(b"0\x1e\r\n"),
+            Some(Match {
+                start: 1,
+                end: 2,
+                pattern_id: 2
+            })
+        );
+
+        let finder = MultiFinder::new(&[b"bcd", b"abcde", b"abc"], false);
+        assert_eq!(
+            finder.find_in(b"xabcdex"),
+            Some(Match {
+                start: 1,
+                end: 6,
+                pattern_id: 1
+            })
+        );
+        assert_eq!(
+            finder.find_in(b"xabcdx"),
+            Some(Match {
+                start: 1,
+                end: 4,
+                pattern_id: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_rfind_in() {
+        let finder = MultiFinder::new(&[b"\r\n", b"\n", b"\x1e"], false);
+        assert_eq!(finder.rfind_in(b""), None);
+        assert_eq!(finder.rfind_in(b"0\r0"), None);
+        assert_eq!(
+            finder.rfind_in(b"\n0\r\n0"),
+            Some(Match {
+                start: 2,
+                end: 4,
+                pattern_id: 0
+            })
+        );
+        assert_eq!(
+            finder.rfind_in(b"\r\n\x1e0"),
+            Some(Match {
+                start: 2,
+                end: 3,
+                pattern_id: 2
+            })
+        );
+
+        let finder = MultiFinder::new(&[b"bcd", b"abcde", b"cde"], false);
+        assert_eq!(
+            finder.rfind_in(b"xabcdex"),
+            Some(Match {
+                start: 1,
+                end: 6,
+                pattern_id: 1
+            })
+        );
+        assert_eq!(
+            finder.rfind_in(b"xbcdex"),
+            Some(Match {
+                start: 2,
+                end: 5,
+                pattern_id: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_find_in_ascii_case_insensitive() {
+        let finder = MultiFinder::new(&[b"Content-Type:", b"Host:"], true);
+        assert_eq!(
+            finder.find_in(b"HOST: x\r\ncontent-type: y"),
+            Some(Match {
+                start: 0,
+                end: 5,
+                pattern_id: 1
+            })
+        );
+        assert_eq!(
+            finder.rfind_in(b"HOST: x\r\ncontent-type: y"),
+            Some(Match {
+                start: 9,
+                end: 22,
+                pattern_id: 0
+            })
+        );
+        assert!(finder.is_built_from(&[b"Content-Type:", b"Host:"], true));
+        assert!(!finder.is_built_from(&[b"Content-Type:", b"Host:"], false));
+    }
+}
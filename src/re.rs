@@ -0,0 +1,135 @@
+use crate::finder::{Match, Search};
+use regex::bytes::Regex;
+use std::ops::Range;
+
+/// A regular expression searched for within the window of a `ByteSeeker`.
+///
+/// A regular expression may match arbitrarily many bytes, so its matches are assumed to be no
+/// longer than `max_len`, which determines how many bytes are carried over between chunks.
+#[derive(Debug)]
+pub(crate) struct RegexSearch<'r> {
+    re: &'r Regex,
+    max_len: usize,
+}
+
+impl<'r> RegexSearch<'r> {
+    pub(crate) fn new(re: &'r Regex, max_len: usize) -> Self {
+        Self { re, max_len }
+    }
+
+    /// Returns the first non-empty match within `haystack`.
+    pub(crate) fn find(&self, haystack: &[u8]) -> Option<Range<usize>> {
+        self.re
+            .find_iter(haystack)
+            .find(|m| !m.is_empty())
+            .map(|m| m.range())
+    }
+
+    /// Returns the non-empty match that ends last within `haystack`, or the longest one if
+    /// several of them end last.
+    ///
+    /// Only the match the regular expression prefers at each offset counts, like with
+    /// `Regex::find_at`, so `a|ab` matches `a` alone: a later alternative never wins over an
+    /// earlier one that matches at the same offset, even if it would end later.
+    pub(crate) fn rfind(&self, haystack: &[u8]) -> Option<Range<usize>> {
+        let last = self
+            .re
+            .find_iter(haystack)
+            .filter(|m| !m.is_empty())
+            .last()?;
+        // No match starts after the last one found, but one starting earlier, within or even
+        // before it, may end later, as long as it is no longer than `max_len`.
+        (last.end().saturating_sub(self.max_len)..last.end())
+            .filter_map(|i| {
+                let end = haystack.len().min(i + self.max_len);
+                self.re
+                    .find_at(&haystack[..end], i)
+                    .filter(|m| m.start() == i && !m.is_empty())
+            })
+            .map(|m| m.range())
+            .fold(None, |found: Option<Range<usize>>, m| match found {
+                Some(found) if found.end >= m.end => Some(found),
+                _ => Some(m),
+            })
+    }
+}
+
+impl<'r> Search for RegexSearch<'r> {
+    fn min_len(&self) -> usize {
+        1
+    }
+
+    fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    fn find_in(&self, haystack: &[u8]) -> Option<Match> {
+        self.find(haystack).map(|m| Match {
+            start: m.start as u64,
+            end: m.end as u64,
+            pattern_id: 0,
+        })
+    }
+
+    fn rfind_in(&self, haystack: &[u8]) -> Option<Match> {
+        self.rfind(haystack).map(|m| Match {
+            start: m.start as u64,
+            end: m.end as u64,
+            pattern_id: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        let re = Regex::new(r"\d+").unwrap();
+        let search = RegexSearch::new(&re, 8);
+        assert_eq!(search.find(b""), None);
+        assert_eq!(search.find(b"abc"), None);
+        assert_eq!(search.find(b"ab12c345"), Some(2..4));
+        assert_eq!(search.rfind(b"ab12c345"), Some(5..8));
+
+        // Empty matches are skipped.
+        let re = Regex::new(r"x*").unwrap();
+        let search = RegexSearch::new(&re, 8);
+        assert_eq!(search.find(b"abxxc"), Some(2..4));
+        assert_eq!(search.rfind(b"abxxc"), Some(2..4));
+        assert_eq!(search.find(b"abc"), None);
+    }
+
+    #[test]
+    fn test_rfind_overlapping() {
+        let re = Regex::new(r"ab|b").unwrap();
+        let search = RegexSearch::new(&re, 8);
+        assert_eq!(search.find(b"_ab_"), Some(1..3));
+        assert_eq!(search.rfind(b"_ab_"), Some(1..3));
+
+        let re = Regex::new(r"ab|bcd").unwrap();
+        let search = RegexSearch::new(&re, 8);
+        assert_eq!(search.find(b"abcd"), Some(0..2));
+        assert_eq!(search.rfind(b"abcd"), Some(1..4));
+
+        // A match starting before the last one found may still end later.
+        let re = Regex::new(r"xy|yzw|z").unwrap();
+        let search = RegexSearch::new(&re, 8);
+        assert_eq!(search.rfind(b"xyzw"), Some(1..4));
+    }
+
+    #[test]
+    fn test_rfind_alternation() {
+        // The preferred alternative wins, even if a later one would end later.
+        let re = Regex::new(r"a|ab").unwrap();
+        let search = RegexSearch::new(&re, 8);
+        assert_eq!(search.find(b"xabx"), Some(1..2));
+        assert_eq!(search.rfind(b"xabx"), Some(1..2));
+
+        let re = Regex::new(r"ab|a").unwrap();
+        let search = RegexSearch::new(&re, 8);
+        assert_eq!(search.find(b"xabx"), Some(1..3));
+        assert_eq!(search.rfind(b"xabx"), Some(1..3));
+    }
+}
@@ -1,7 +1,21 @@
 use crate::error::{Error, ErrorKind, Result};
-use std::io::{Read, Seek, SeekFrom};
+use crate::finder::{Finder, Match, Search};
+use crate::iter::{FindIter, LinesBack, Split};
+use crate::masked::MaskedPattern;
+use crate::multi::MultiFinder;
+#[cfg(feature = "regex")]
+use crate::re::RegexSearch;
+use crate::window::{Refill, Step, Window};
+#[cfg(feature = "regex")]
+use regex::bytes::Regex;
+use std::convert::TryFrom;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::iter::Rev;
+use std::ops::Range;
+use std::thread;
+use std::time::{Duration, Instant};
 
-const DEFAULT_CHUNK_SIZE: usize = 1024;
+pub(crate) const DEFAULT_CHUNK_SIZE: usize = 1024;
 
 /// Seeker that can seek the occurences of a given byte slice within a stream of bytes.
 ///
@@ -24,6 +38,24 @@ const DEFAULT_CHUNK_SIZE: usize = 1024;
 /// assert_eq!(seeker.seek_nth_back(b"0", 1).unwrap(), 0);
 /// ```
 ///
+/// Forward and backward searches have their own cursors, which move towards each other and never
+/// cross. This means you can alternately seek from both ends of the stream, and each occurence is
+/// found at most once:
+///
+/// ```
+/// use byteseeker::ByteSeeker;
+/// use std::io::Cursor;
+///
+/// let bytes = b"0\n1\n2\n3";
+/// let mut cursor = Cursor::new(bytes);
+/// let mut seeker = ByteSeeker::new(&mut cursor);
+///
+/// assert_eq!(seeker.seek(b"\n").unwrap(), 1);
+/// assert_eq!(seeker.seek_back(b"\n").unwrap(), 5);
+/// assert_eq!(seeker.seek(b"\n").unwrap(), 3);
+/// assert_eq!(seeker.seek_back(b"\n").is_err(), true);
+/// ```
+///
 /// The `ByteSeeker` uses a internal buffer to read a chunk of bytes into memory to search the
 /// occurences of a given byte slice. You can specify the capacity of the interal buffer by
 /// initializing a `ByteSeeker` using `ByteSeeker::with_capacity`, if you are seeking within a
@@ -31,22 +63,106 @@ const DEFAULT_CHUNK_SIZE: usize = 1024;
 /// `1024` currently.
 ///
 /// It's worth noting that seeking a byte slice whose length is greater than the `capacity` of the
-/// calling `ByteSeeker` is not allowed.
+/// calling `ByteSeeker` is not allowed. If you want to read large chunks for throughput while only
+/// seeking short byte slices, keep the `capacity` small and raise the size of the chunks read at
+/// once using `ByteSeeker::set_chunk_size`.
+///
+/// A `ByteSeeker` can either borrow the underlying byte stream, as above, or own it, in which case
+/// it can be stored or sent along with it and unwrapped using `ByteSeeker::into_inner`:
+///
+/// ```
+/// use byteseeker::ByteSeeker;
+/// use std::io::Cursor;
+///
+/// fn seeker_of(bytes: Vec<u8>) -> ByteSeeker<Cursor<Vec<u8>>> {
+///     ByteSeeker::new(Cursor::new(bytes))
+/// }
+///
+/// let mut seeker = seeker_of(b"0\n1\n".to_vec());
+/// assert_eq!(seeker.seek_back(b"\n").unwrap(), 3);
+/// ```
 #[derive(Debug)]
-pub struct ByteSeeker<'a, RS: 'a + Read + Seek> {
-    inner: &'a mut RS,
-    buf: Vec<u8>,
-    len: usize,
+pub struct ByteSeeker<R: Read + Seek> {
+    inner: R,
+    // The current position of `inner`, if known.
+    stream_pos: Option<u64>,
+    // A window of the underlying stream, holding the bytes read last.
+    window: Window,
+    // The finder of the last `seek` or `seek_back` call.
+    byte_finder: Option<Finder>,
+    // The automaton of the last `seek_any` or `seek_any_back` call.
+    multi_finder: Option<MultiFinder>,
+    len: u64,
+    // The part of the underlying stream searches are restricted to.
+    range: Range<u64>,
+    // The requested end of `range`, which may lie past the end of the stream.
+    range_end: u64,
+    // The maximum length of the byte slices to seek.
     cap: usize,
+    // The number of bytes read from `inner` at once, besides the carried over ones.
+    chunk_size: usize,
     state: State,
+    ignore_case: bool,
+    match_mode: MatchMode,
+    change_policy: ChangePolicy,
 }
 
 #[derive(Clone, Copy, Debug)]
-struct State {
-    lpos: usize,
-    rpos: usize,
-    done: bool,
-    last: bool,
+pub(crate) struct State {
+    // The offset where the next forward search starts.
+    pub(crate) lpos: u64,
+    // The offset (exclusive) where the next backward search ends.
+    pub(crate) rpos: u64,
+}
+
+impl State {
+    // Moves the cursor of the given direction past the found match, or just past its first byte
+    // when matches may overlap.
+    pub(crate) fn advance(&mut self, m: &Match, dir: Dir, mode: MatchMode) {
+        match (dir, mode) {
+            (Dir::Start, MatchMode::NonOverlapping) => self.lpos = m.end,
+            (Dir::Start, MatchMode::Overlapping) => self.lpos = m.start + 1,
+            (Dir::End, MatchMode::NonOverlapping) => self.rpos = m.start,
+            (Dir::End, MatchMode::Overlapping) => self.rpos = m.end - 1,
+        }
+    }
+}
+
+/// Whether the occurences found by consecutive searches of a `ByteSeeker` in the same direction
+/// may overlap each other.
+///
+/// This is set using [`ByteSeeker::set_match_mode`].
+///
+/// [`ByteSeeker::set_match_mode`]: struct.ByteSeeker.html#method.set_match_mode
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MatchMode {
+    /// The next search starts right after the last found occurence, so `b"\n\n"` occurs twice
+    /// within `b"\n\n\n\n"`, in either direction. This is the default.
+    #[default]
+    NonOverlapping,
+    /// The next search starts one byte after the start (or before the end, when searching
+    /// backwards) of the last found occurence, so `b"\n\n"` occurs three times within
+    /// `b"\n\n\n\n"`, in either direction.
+    Overlapping,
+}
+
+/// What a `ByteSeeker` does when the length of the underlying byte stream turns out to have
+/// changed during a search, or to have shrunk when it is refreshed.
+///
+/// This is set using [`ByteSeeker::set_change_policy`].
+///
+/// [`ByteSeeker::set_change_policy`]: struct.ByteSeeker.html#method.set_change_policy
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChangePolicy {
+    /// Fails the search with an error variant of `ErrorKind::StreamChanged`. This is the default.
+    #[default]
+    Error,
+    /// Measures the length of the byte stream again like `ByteSeeker::refresh`, and continues
+    /// the search from where it was.
+    Remeasure,
+    /// Measures the length of the byte stream again like `ByteSeeker::refresh`, and restarts the
+    /// search from the corresponding end of the byte stream, as if the `ByteSeeker` was reset.
+    Restart,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -55,9 +171,14 @@ pub enum Dir {
     End,
 }
 
-impl<'a, RS: 'a + Read + Seek> ByteSeeker<'a, RS> {
+impl<R: Read + Seek> ByteSeeker<R> {
     /// Creates a new `ByteSeeker` that wraps a byte stream that implements `Read` and `Seek`.
     ///
+    /// # Panics
+    ///
+    /// Panics if the length of the given byte stream cannot be determined by seeking it. See
+    /// [`try_new`] for a fallible alternative.
+    ///
     /// # Examples
     ///
     /// ```
@@ -68,13 +189,24 @@ impl<'a, RS: 'a + Read + Seek> ByteSeeker<'a, RS> {
     /// let mut cursor = Cursor::new(bytes);
     /// let mut seeker = ByteSeeker::new(&mut cursor);
     /// ```
-    pub fn new(stream: &'a mut RS) -> Self {
+    ///
+    /// [`try_new`]: #method.try_new
+    pub fn new(stream: R) -> Self {
         ByteSeeker::with_capacity(stream, DEFAULT_CHUNK_SIZE)
     }
 
     /// Creates a new `ByteSeeker` that wraps a byte stream that implements `Read` and `Seek`, and
     /// sets the `capacity` of its internal buffer to the given capacity.
     ///
+    /// The maximum length of the byte slices to seek is set to `cap`, while the chunks read from
+    /// the byte stream are `cap` or `1024` bytes long, whichever is larger. Use
+    /// [`set_chunk_size`] to change the latter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the given byte stream cannot be determined by seeking it. See
+    /// [`try_with_capacity`] for a fallible alternative.
+    ///
     /// # Examples
     ///
     /// ```
@@ -85,26 +217,148 @@ impl<'a, RS: 'a + Read + Seek> ByteSeeker<'a, RS> {
     /// let mut cursor = Cursor::new(bytes);
     /// let mut seeker = ByteSeeker::with_capacity(&mut cursor, 3);
     /// ```
-    pub fn with_capacity(stream: &'a mut RS, cap: usize) -> Self {
-        // SAFETY: safe because `SeekFrom::End(0)` cannot return error.
-        let len = stream.seek(SeekFrom::End(0)).unwrap() as usize;
-        stream.seek(SeekFrom::Start(0)).unwrap();
+    ///
+    /// [`set_chunk_size`]: #method.set_chunk_size
+    /// [`try_with_capacity`]: #method.try_with_capacity
+    pub fn with_capacity(stream: R, cap: usize) -> Self {
+        match ByteSeeker::try_with_capacity(stream, cap) {
+            Ok(seeker) => seeker,
+            Err(e) => panic!("failed to measure the length of the byte stream: {}", e),
+        }
+    }
+
+    /// Creates a new `ByteSeeker` that wraps a byte stream that implements `Read` and `Seek`,
+    /// or returns an error if the length of the byte stream cannot be determined.
+    ///
+    /// # Errors
+    ///
+    /// If seeking the given byte stream fails, an error variant of `ErrorKind::Io` will be
+    /// returned. This may happen for pipes, sockets, or custom readers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = [1, 2, 3];
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::try_new(&mut cursor).unwrap();
+    /// ```
+    pub fn try_new(stream: R) -> Result<Self> {
+        ByteSeeker::try_with_capacity(stream, DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Creates a new `ByteSeeker` that wraps a byte stream that implements `Read` and `Seek`, and
+    /// sets the `capacity` of its internal buffer to the given capacity, or returns an error if
+    /// the length of the byte stream cannot be determined.
+    ///
+    /// # Errors
+    ///
+    /// See [`try_new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = [1, 2, 3];
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::try_with_capacity(&mut cursor, 3).unwrap();
+    /// ```
+    ///
+    /// [`try_new`]: #method.try_new
+    pub fn try_with_capacity(stream: R, cap: usize) -> Result<Self> {
+        ByteSeeker::try_with_parts(stream, cap, 0..u64::MAX)
+    }
+
+    /// Creates a new `ByteSeeker` that wraps a byte stream that implements `Read` and `Seek`, and
+    /// restricts all searches to the given range of the byte stream.
+    ///
+    /// Bytes outside of `range` are never read, and occurences that are not fully within `range`
+    /// are not found. Offsets are still relative to the start of the underlying byte stream. The
+    /// end of `range` is clamped to the length of the byte stream.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the given byte stream cannot be determined by seeking it. See
+    /// [`try_with_range`] for a fallible alternative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"0\n1\n2\n";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::with_range(&mut cursor, 2..5);
+    ///
+    /// assert_eq!(seeker.seek(b"\n").unwrap(), 3);
+    /// assert_eq!(seeker.seek(b"\n").is_err(), true);
+    /// ```
+    ///
+    /// [`try_with_range`]: #method.try_with_range
+    pub fn with_range(stream: R, range: Range<u64>) -> Self {
+        match ByteSeeker::try_with_range(stream, range) {
+            Ok(seeker) => seeker,
+            Err(e) => panic!("failed to measure the length of the byte stream: {}", e),
+        }
+    }
+
+    /// Creates a new `ByteSeeker` that wraps a byte stream that implements `Read` and `Seek`, and
+    /// restricts all searches to the given range of the byte stream, or returns an error if the
+    /// length of the byte stream cannot be determined.
+    ///
+    /// # Errors
+    ///
+    /// See [`try_new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"0\n1\n2\n";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::try_with_range(&mut cursor, 2..5).unwrap();
+    /// ```
+    ///
+    /// [`try_new`]: #method.try_new
+    pub fn try_with_range(stream: R, range: Range<u64>) -> Result<Self> {
+        ByteSeeker::try_with_parts(stream, DEFAULT_CHUNK_SIZE, range)
+    }
+
+    fn try_with_parts(mut stream: R, cap: usize, range: Range<u64>) -> Result<Self> {
+        let len = stream.seek(SeekFrom::End(0))?;
+        stream.seek(SeekFrom::Start(0))?;
 
-        let rpos = if len == 0 { 0 } else { len - 1 };
+        let range_end = range.end;
+        let range = clamp_range(range, len);
         let state = State {
-            lpos: 0,
-            rpos: rpos,
-            last: false,
-            done: false,
+            lpos: range.start,
+            rpos: range.end,
         };
 
-        Self {
+        Ok(Self {
             len,
+            range,
+            range_end,
             cap,
+            chunk_size: DEFAULT_CHUNK_SIZE.max(cap),
             state,
             inner: stream,
-            buf: vecu8(DEFAULT_CHUNK_SIZE),
-        }
+            stream_pos: Some(0),
+            // The buffer is only allocated once the first search knows how much it needs.
+            window: Window::new(0),
+            byte_finder: None,
+            multi_finder: None,
+            ignore_case: false,
+            match_mode: MatchMode::default(),
+            change_policy: ChangePolicy::default(),
+        })
     }
 
     /// Returns the length of the underlying byte stream.
@@ -121,11 +375,30 @@ impl<'a, RS: 'a + Read + Seek> ByteSeeker<'a, RS> {
     ///
     /// assert_eq!(seeker.len(), 11);
     /// ```
-    pub fn len(&self) -> usize {
+    pub fn len(&self) -> u64 {
         self.len
     }
 
-    /// Returns the capacity of this `ByteSeeker`.
+    /// Returns `true` if the underlying byte stream is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = "".as_bytes();
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// assert!(seeker.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the capacity of this `ByteSeeker`, which is the maximum length of the byte slices
+    /// it can seek.
     ///
     /// # Examples
     ///
@@ -143,8 +416,8 @@ impl<'a, RS: 'a + Read + Seek> ByteSeeker<'a, RS> {
         self.cap
     }
 
-    /// Resets the state of the `ByteSeeker` to its original, so you can reuse this initialized
-    /// `ByteSeeker` as it was newly created.
+    /// Returns the number of bytes this `ByteSeeker` reads from the underlying byte stream at
+    /// once.
     ///
     /// # Examples
     ///
@@ -152,45 +425,26 @@ impl<'a, RS: 'a + Read + Seek> ByteSeeker<'a, RS> {
     /// use byteseeker::ByteSeeker;
     /// use std::io::Cursor;
     ///
-    /// let bytes = [b'0', b'\n', b'0'];
+    /// let bytes = "lorem ipsum".as_bytes();
     /// let mut cursor = Cursor::new(bytes);
-    /// let mut seeker = ByteSeeker::new(&mut cursor);
-    ///
-    /// assert_eq!(seeker.seek(b"0").unwrap(), 0);
-    /// assert_eq!(seeker.seek(b"0").unwrap(), 2);
+    /// let mut seeker = ByteSeeker::with_capacity(&mut cursor, 11);
     ///
-    /// // The `reset` here is equivalent to:
-    /// // let mut seeker = ByteSeeker::new(&mut cursor);
-    /// seeker.reset();
-    /// assert_eq!(seeker.seek_back(b"0").unwrap(), 2);
-    /// assert_eq!(seeker.seek_back(b"0").unwrap(), 0);
+    /// assert_eq!(seeker.chunk_size(), 1024);
     /// ```
-    pub fn reset(&mut self) {
-        self.inner.seek(SeekFrom::Start(0)).unwrap();
-
-        let rpos = if self.len == 0 { 0 } else { self.len - 1 };
-        self.state = State {
-            lpos: 0,
-            rpos: rpos,
-            last: false,
-            done: false,
-        }
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
     }
 
-    /// Searches for the given bytes **forwards**, and returns the offset (ralative to the start
-    /// of the underlying byte stream) if the given bytes were found.
-    ///
-    /// If the initialized `ByteSeeker` haven't been called before, `seek` will start from
-    /// the beginning; Otherwise, it will start from the last found `seek` position + 1.
+    /// Sets the number of bytes this `ByteSeeker` reads from the underlying byte stream at once.
     ///
-    /// The `ByteSeeker` is stateful, which means you can call `seek` multiple times until
-    /// reaching the end of the underlying byte stream.
+    /// Unlike the `capacity`, the chunk size does not limit the length of the byte slices to
+    /// seek: the last `bytes.len() - 1` bytes of a chunk are carried over to the next one, so
+    /// that occurences spanning two chunks are still found.
     ///
-    /// # Errors
+    /// # Panics
     ///
-    /// If the given bytes were not found, an error variant of `ErrorKind::ByteNotFound` will be
-    /// returned. If any other I/O errors were encountered, an error variant of `ErrorKind::Io`
-    /// will be returned.
+    /// Panics if `chunk_size` is zero. Larger chunk sizes, even `usize::MAX`, are fine: no more
+    /// bytes are buffered than the range this `ByteSeeker` is restricted to holds.
     ///
     /// # Examples
     ///
@@ -198,32 +452,46 @@ impl<'a, RS: 'a + Read + Seek> ByteSeeker<'a, RS> {
     /// use byteseeker::ByteSeeker;
     /// use std::io::Cursor;
     ///
-    /// let bytes = [b'0', b'\n', b'\n', b'\n'];
+    /// let bytes = "lorem ipsum".as_bytes();
     /// let mut cursor = Cursor::new(bytes);
-    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    /// let mut seeker = ByteSeeker::with_capacity(&mut cursor, 5);
+    /// seeker.set_chunk_size(64 * 1024);
     ///
-    /// assert_eq!(seeker.seek(b"0").unwrap(), 0);
-    /// assert_eq!(seeker.seek(b"\n\n").unwrap(), 1);
-    /// assert_eq!(seeker.seek(b"\n\n").is_err(), true);
+    /// assert_eq!(seeker.capacity(), 5);
+    /// assert_eq!(seeker.chunk_size(), 64 * 1024);
+    /// assert_eq!(seeker.seek(b"ipsum").unwrap(), 6);
     /// ```
-    pub fn seek(&mut self, bytes: &[u8]) -> Result<usize> {
-        self.buf_seek(bytes, Dir::Start)
+    pub fn set_chunk_size(&mut self, chunk_size: usize) {
+        assert!(chunk_size > 0, "the chunk size must be positive");
+        self.chunk_size = chunk_size;
     }
 
-    /// Searches for the given bytes **backwards**, and returns the offset (ralative to the start
-    /// of the underlying byte stream) if the given bytes were found.
+    /// Returns the range of the underlying byte stream this `ByteSeeker` is restricted to.
     ///
-    /// If the initialized `ByteSeeker` haven't been called before, `seek` will start from
-    /// the end; Otherwise, it will start from the last found `seek` position - 1.
+    /// # Examples
     ///
-    /// The `ByteSeeker` is stateful, which means you can call `seek_back` multiple times until
-    /// reaching the end of the underlying byte stream.
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
     ///
-    /// # Errors
+    /// let bytes = "lorem ipsum".as_bytes();
+    /// let mut cursor = Cursor::new(bytes);
     ///
-    /// If the given bytes were not found, an error variant of `ErrorKind::ByteNotFound` will be
-    /// returned. If any other I/O errors were encountered, an error variant of `ErrorKind::Io`
-    /// will be returned.
+    /// let seeker = ByteSeeker::new(&mut cursor);
+    /// assert_eq!(seeker.range(), 0..11);
+    ///
+    /// let seeker = ByteSeeker::with_range(&mut cursor, 6..42);
+    /// assert_eq!(seeker.range(), 6..11);
+    /// ```
+    pub fn range(&self) -> Range<u64> {
+        self.range.clone()
+    }
+
+    /// Restricts all searches to the given range of the underlying byte stream, and resets both
+    /// cursors to the ends of that range.
+    ///
+    /// Bytes outside of `range` are never read, and occurences that are not fully within `range`
+    /// are not found. The end of `range` is clamped to the length of the byte stream.
     ///
     /// # Examples
     ///
@@ -231,310 +499,1837 @@ impl<'a, RS: 'a + Read + Seek> ByteSeeker<'a, RS> {
     /// use byteseeker::ByteSeeker;
     /// use std::io::Cursor;
     ///
-    /// let bytes = [b'0', b'\n', b'\n', b'\n'];
+    /// // Two blobs of 4 bytes each, concatenated.
+    /// let bytes = b"0\n1\n2\n3\n";
     /// let mut cursor = Cursor::new(bytes);
     /// let mut seeker = ByteSeeker::new(&mut cursor);
     ///
-    /// assert_eq!(seeker.seek_back(b"\n\n").unwrap(), 2);
-    /// assert_eq!(seeker.seek_back(b"\n\n").is_err(), true);
+    /// seeker.set_range(0..4);
+    /// assert_eq!(seeker.seek_back(b"\n").unwrap(), 3);
+    /// seeker.set_range(4..8);
+    /// assert_eq!(seeker.seek_back(b"\n").unwrap(), 7);
     /// ```
-    pub fn seek_back(&mut self, bytes: &[u8]) -> Result<usize> {
-        self.buf_seek(bytes, Dir::End)
+    pub fn set_range(&mut self, range: Range<u64>) {
+        self.range_end = range.end;
+        self.range = clamp_range(range, self.len);
+        self.state = State {
+            lpos: self.range.start,
+            rpos: self.range.end,
+        };
     }
 
-    /// Seeks the nth occurence of the given bytes **forwards**, and returns the offset (ralative
-    /// to the start of the underlying byte stream) if the given bytes were found.
-    ///
-    /// If the initialized `ByteSeeker` haven't been called before, `seek_nth`
-    /// will start from the beginning; Otherwise, it will start from the last found `seek`
-    /// position + 1.
-    ///
-    /// The `ByteSeeker` is stateful, which means you can call `seek_nth` multiple times until
-    /// reaching the end of the underlying byte stream.
-    ///
-    /// # Errors
-    ///
-    /// If the given bytes were not found, an error variant of `ErrorKind::ByteNotFound` will be
-    /// returned. If any other I/O errors were encountered, an error variant of `ErrorKind::Io`
-    /// will be returned.
+    /// Returns the offset where the next forward search starts.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::io::Cursor;
     /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
     ///
-    /// let mut bytes = [b'\n', b'\n', b'\n', b'\n', b'\n'];
+    /// let bytes = b"0\n1\n";
     /// let mut cursor = Cursor::new(bytes);
     /// let mut seeker = ByteSeeker::new(&mut cursor);
-    /// assert_eq!(seeker.seek_nth(b"\n\n", 2).unwrap(), 2);
-    /// assert_eq!(seeker.seek_nth(b"\n\n", 2).is_err(), true);
+    ///
+    /// assert_eq!(seeker.position(), 0);
+    /// assert_eq!(seeker.seek(b"\n").unwrap(), 1);
+    /// assert_eq!(seeker.position(), 2);
     /// ```
-    pub fn seek_nth(&mut self, bytes: &[u8], nth: usize) -> Result<usize> {
-        let mut counter = nth;
-        loop {
-            let pos = self.seek(bytes)?;
-            match counter.checked_sub(1) {
-                None => {
-                    return Err(Error::new(ErrorKind::ByteNotFound));
-                }
-                Some(0) => {
-                    return Ok(pos);
-                }
-                Some(n) => {
-                    counter = n;
-                }
-            }
-        }
+    pub fn position(&self) -> u64 {
+        self.state.lpos
     }
 
-    /// Seeks the nth occurence of the given bytes **backwards**, and returns the offset (ralative
-    /// to the start of the underlying byte stream) if the given bytes were found.
-    ///
-    /// If the initialized `ByteSeeker` haven't been called before, `seek_nth_back`
-    /// will start from the end; Otherwise, it will start from the last found `seek` position - 1.
-    ///
-    /// The `ByteSeeker` is stateful, which means you can call `seek_nth_back` multiple times until
-    /// reaching the end of the underlying byte stream.
+    /// Sets the offset where the next forward search starts, without touching the cursor of
+    /// backward searches.
     ///
-    /// # Errors
-    ///
-    /// If the given bytes were not found, an error variant of `ErrorKind::ByteNotFound` will be
-    /// returned. If any other I/O errors were encountered, an error variant of `ErrorKind::Io`
-    /// will be returned.
+    /// Offsets outside of the range this `ByteSeeker` is restricted to (which is the whole
+    /// underlying byte stream by default) are clamped into it.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::io::Cursor;
     /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
     ///
-    /// let mut bytes = [b'\n', b'\n', b'\n', b'\n', b'\n'];
+    /// let bytes = b"0\n1\n2\n";
     /// let mut cursor = Cursor::new(bytes);
     /// let mut seeker = ByteSeeker::new(&mut cursor);
-    /// assert_eq!(seeker.seek_nth_back(b"\n\n", 2).unwrap(), 1);
-    /// assert_eq!(seeker.seek_nth_back(b"\n\n", 2).is_err(), true);
+    ///
+    /// seeker.set_position(2);
+    /// assert_eq!(seeker.seek(b"\n").unwrap(), 3);
     /// ```
-    pub fn seek_nth_back(&mut self, bytes: &[u8], nth: usize) -> Result<usize> {
-        let mut counter = nth;
-        loop {
-            let pos = self.seek_back(bytes)?;
-            match counter.checked_sub(1) {
-                None => {
-                    return Err(Error::new(ErrorKind::ByteNotFound));
-                }
-                Some(0) => {
-                    return Ok(pos);
-                }
-                Some(n) => {
-                    counter = n;
-                }
-            }
-        }
+    pub fn set_position(&mut self, pos: u64) {
+        self.state.lpos = pos.max(self.range.start).min(self.range.end);
     }
 
-    /// Gets a mutable reference to the underlying reader.
-    ///
-    /// It is inadvisable to directly read from the underlying reader.
+    /// Returns the offset (exclusive) where the next backward search ends.
     ///
     /// # Examples
     ///
     /// ```
     /// use byteseeker::ByteSeeker;
-    /// use std::io::{Cursor, Read};
+    /// use std::io::Cursor;
     ///
-    /// let bytes = [b'0', b'\n', b'\n', b'\n'];
+    /// let bytes = b"0\n1\n";
     /// let mut cursor = Cursor::new(bytes);
     /// let mut seeker = ByteSeeker::new(&mut cursor);
-    /// assert_eq!(seeker.seek(b"\n").unwrap(), 1);
     ///
-    /// let mut reader = seeker.get_mut();
+    /// assert_eq!(seeker.back_position(), 4);
+    /// assert_eq!(seeker.seek_back(b"\n").unwrap(), 3);
+    /// assert_eq!(seeker.back_position(), 3);
+    /// ```
+    pub fn back_position(&self) -> u64 {
+        self.state.rpos
+    }
+
+    /// Sets the offset (exclusive) where the next backward search ends, without touching the
+    /// cursor of forward searches.
+    ///
+    /// Offsets outside of the range this `ByteSeeker` is restricted to (which is the whole
+    /// underlying byte stream by default) are clamped into it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"0\n1\n2\n";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// seeker.set_back_position(4);
+    /// assert_eq!(seeker.seek_back(b"\n").unwrap(), 3);
+    /// ```
+    pub fn set_back_position(&mut self, pos: u64) {
+        self.state.rpos = pos.max(self.range.start).min(self.range.end);
+    }
+
+    /// Resets the state of the `ByteSeeker` to its original, so you can reuse this initialized
+    /// `ByteSeeker` as it was newly created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying byte stream cannot be rewound. See [`try_reset`] for a fallible
+    /// alternative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = [b'0', b'\n', b'0'];
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// assert_eq!(seeker.seek(b"0").unwrap(), 0);
+    /// assert_eq!(seeker.seek(b"0").unwrap(), 2);
+    ///
+    /// // The `reset` here is equivalent to:
+    /// // let mut seeker = ByteSeeker::new(&mut cursor);
+    /// seeker.reset();
+    /// assert_eq!(seeker.seek_back(b"0").unwrap(), 2);
+    /// assert_eq!(seeker.seek_back(b"0").unwrap(), 0);
+    /// ```
+    ///
+    /// [`try_reset`]: #method.try_reset
+    pub fn reset(&mut self) {
+        if let Err(e) = self.try_reset() {
+            panic!("failed to rewind the byte stream: {}", e);
+        }
+    }
+
+    /// Resets the state of the `ByteSeeker` to its original, or returns an error if the
+    /// underlying byte stream cannot be rewound.
+    ///
+    /// # Errors
+    ///
+    /// If seeking the underlying byte stream fails, an error variant of `ErrorKind::Io` will be
+    /// returned, and the state of the `ByteSeeker` is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = [b'0', b'\n', b'0'];
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// assert_eq!(seeker.seek(b"0").unwrap(), 0);
+    /// seeker.try_reset().unwrap();
+    /// assert_eq!(seeker.seek(b"0").unwrap(), 0);
+    /// ```
+    pub fn try_reset(&mut self) -> Result<()> {
+        self.stream_pos = None;
+        self.inner.seek(SeekFrom::Start(0))?;
+        self.stream_pos = Some(0);
+
+        self.state = State {
+            lpos: self.range.start,
+            rpos: self.range.end,
+        };
+        Ok(())
+    }
+
+    /// Measures the length of the underlying byte stream again, so that bytes appended to it
+    /// since this `ByteSeeker` was created (or last refreshed) can be searched, and returns the
+    /// new length.
+    ///
+    /// The range this `ByteSeeker` is restricted to grows along with the byte stream, up to the
+    /// end it was given. If the back cursor was at the end of that range, it moves to the new
+    /// end, so backward searches start from the end of the grown byte stream.
+    ///
+    /// If the byte stream has shrunk instead, for example because a log file was truncated or
+    /// rotated, the [`ChangePolicy`] of this `ByteSeeker` is applied, just as if the change was
+    /// noticed during a search.
+    ///
+    /// # Errors
+    ///
+    /// If seeking the underlying byte stream fails, an error variant of `ErrorKind::Io` will be
+    /// returned, and the state of the `ByteSeeker` is left untouched.
+    ///
+    /// If the byte stream has shrunk and the change policy is `ChangePolicy::Error`, an error
+    /// variant of `ErrorKind::StreamChanged` will be returned, and the state of the `ByteSeeker`
+    /// is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let mut seeker = ByteSeeker::new(Cursor::new(b"0\n1".to_vec()));
+    /// assert_eq!(seeker.seek(b"\n").unwrap(), 1);
+    /// assert_eq!(seeker.seek(b"\n").is_err(), true);
+    ///
+    /// seeker.get_mut().get_mut().extend_from_slice(b"\n2");
+    /// assert_eq!(seeker.refresh().unwrap(), 5);
+    /// assert_eq!(seeker.seek(b"\n").unwrap(), 3);
+    /// ```
+    ///
+    /// [`ChangePolicy`]: enum.ChangePolicy.html
+    pub fn refresh(&mut self) -> Result<u64> {
+        let len = self.measure()?;
+        if len < self.len {
+            // The bytes read so far may be stale, whatever the policy.
+            self.window.clear();
+            self.change_len(len)?;
+        } else {
+            self.set_len(len);
+        }
+        Ok(len)
+    }
+
+    /// Returns `true` if the searches of this `ByteSeeker` ignore the case of ASCII letters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let mut cursor = Cursor::new(b"0\n");
+    /// let seeker = ByteSeeker::new(&mut cursor);
+    /// assert_eq!(seeker.is_ascii_case_insensitive(), false);
+    /// ```
+    pub fn is_ascii_case_insensitive(&self) -> bool {
+        self.ignore_case
+    }
+
+    /// Sets whether the searches of this `ByteSeeker` ignore the case of ASCII letters.
+    ///
+    /// This applies to every method that takes the bytes to search for, including `seek_any` and
+    /// the iterators, but not to `seek_with` and `seek_back_with`, which use the given `Finder`
+    /// as is. The underlying byte stream is compared byte by byte rather than lowercased.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"Host: x\r\nCONTENT-TYPE: a\r\ncontent-type: b\r\n";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    /// seeker.set_ascii_case_insensitive(true);
+    ///
+    /// assert_eq!(seeker.seek(b"Content-Type:").unwrap(), 9);
+    /// assert_eq!(seeker.seek_back(b"Content-Type:").unwrap(), 26);
+    /// ```
+    pub fn set_ascii_case_insensitive(&mut self, yes: bool) {
+        self.ignore_case = yes;
+    }
+
+    /// Returns whether the occurences found by consecutive searches may overlap each other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::{ByteSeeker, MatchMode};
+    /// use std::io::Cursor;
+    ///
+    /// let mut cursor = Cursor::new(b"0\n");
+    /// let seeker = ByteSeeker::new(&mut cursor);
+    /// assert_eq!(seeker.match_mode(), MatchMode::NonOverlapping);
+    /// ```
+    pub fn match_mode(&self) -> MatchMode {
+        self.match_mode
+    }
+
+    /// Sets whether the occurences found by consecutive searches may overlap each other, in
+    /// either direction.
+    ///
+    /// This applies to `seek`, `seek_back`, `seek_nth`, `seek_nth_back` and the other searches
+    /// that return offsets, but not to the methods that read or split on the found occurences,
+    /// which always consume them whole. See [`MatchMode`] for the available modes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::{ByteSeeker, MatchMode};
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"\n\n\n\n";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    /// seeker.set_match_mode(MatchMode::Overlapping);
+    ///
+    /// assert_eq!(seeker.seek(b"\n\n").unwrap(), 0);
+    /// assert_eq!(seeker.seek(b"\n\n").unwrap(), 1);
+    /// assert_eq!(seeker.seek_back(b"\n\n").unwrap(), 2);
+    /// assert_eq!(seeker.seek_back(b"\n\n").is_err(), true);
+    /// ```
+    ///
+    /// [`MatchMode`]: enum.MatchMode.html
+    pub fn set_match_mode(&mut self, mode: MatchMode) {
+        self.match_mode = mode;
+    }
+
+    /// Returns the policy applied when the length of the underlying byte stream turns out to
+    /// have changed during a search.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::{ByteSeeker, ChangePolicy};
+    /// use std::io::Cursor;
+    ///
+    /// let mut cursor = Cursor::new(b"0\n");
+    /// let seeker = ByteSeeker::new(&mut cursor);
+    /// assert_eq!(seeker.change_policy(), ChangePolicy::Error);
+    /// ```
+    pub fn change_policy(&self) -> ChangePolicy {
+        self.change_policy
+    }
+
+    /// Sets the policy applied when the length of the underlying byte stream turns out to have
+    /// changed during a search, for example because a log file was truncated or rotated.
+    ///
+    /// The change is noticed when the byte stream ends earlier than expected. See
+    /// [`ChangePolicy`] for the available policies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::{ByteSeeker, ChangePolicy};
+    /// use std::io::Cursor;
+    ///
+    /// let mut seeker = ByteSeeker::new(Cursor::new(b"0\n1\n2\n".to_vec()));
+    /// seeker.set_change_policy(ChangePolicy::Restart);
+    /// assert_eq!(seeker.seek(b"\n").unwrap(), 1);
+    ///
+    /// // The stream is rotated behind the back of the `ByteSeeker`.
+    /// let stream = seeker.get_mut().get_mut();
+    /// stream.clear();
+    /// stream.extend_from_slice(b"3\n");
+    ///
+    /// assert_eq!(seeker.seek(b"\n").unwrap(), 1);
+    /// ```
+    ///
+    /// [`ChangePolicy`]: enum.ChangePolicy.html
+    pub fn set_change_policy(&mut self, policy: ChangePolicy) {
+        self.change_policy = policy;
+    }
+
+    /// Searches for the given bytes **forwards** like `seek`, and if they were not found, waits
+    /// for the underlying byte stream to grow until they are, like `tail -f` does.
+    ///
+    /// The length of the byte stream is measured again using [`refresh`] every `interval`, and
+    /// the search continues into the newly appended bytes. This blocks the current thread until
+    /// the given bytes were found or an error occurs. See [`seek_follow_timeout`] to give up
+    /// after a while.
+    ///
+    /// Appended bytes lie past the backward cursor, so it is moved to the end of the range before
+    /// searching, even if [`seek_back`] moved it before. If the byte stream shrinks while
+    /// waiting, the change policy is applied like [`refresh`] does, so with
+    /// `ChangePolicy::Restart` the search starts over from the start of the range.
+    ///
+    /// # Errors
+    ///
+    /// If the given bytes are invalid, an error variant of `ErrorKind::UnsupportedLength` will be
+    /// returned. If the given bytes were not found and the range this `ByteSeeker` is restricted
+    /// to cannot grow any further, an error variant of `ErrorKind::ByteNotFound` will be
+    /// returned. If the byte stream shrinks and the change policy is `ChangePolicy::Error`, an
+    /// error variant of `ErrorKind::StreamChanged` will be returned. If any I/O errors were
+    /// encountered, an error variant of `ErrorKind::Io` will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use byteseeker::ByteSeeker;
+    /// use std::fs::File;
+    /// use std::time::Duration;
+    ///
+    /// let file = File::open("app.log").unwrap();
+    /// let mut seeker = ByteSeeker::new(file);
+    /// loop {
+    ///     let pos = seeker.seek_follow(b"\n", Duration::from_millis(500)).unwrap();
+    ///     println!("a line ends at {}", pos);
+    /// }
+    /// ```
+    ///
+    /// [`refresh`]: #method.refresh
+    /// [`seek_back`]: #method.seek_back
+    /// [`seek_follow_timeout`]: #method.seek_follow_timeout
+    pub fn seek_follow(&mut self, bytes: &[u8], interval: Duration) -> Result<u64> {
+        self.follow(bytes, interval, None)
+    }
+
+    /// Like [`seek_follow`], but gives up once the given bytes were not found within `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// If the given bytes were not found within `timeout`, an error variant of
+    /// `ErrorKind::ByteNotFound` will be returned. See [`seek_follow`] for the other errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    /// use std::time::Duration;
+    ///
+    /// let mut cursor = Cursor::new(b"0\n1");
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// let interval = Duration::from_millis(1);
+    /// let timeout = Duration::from_millis(10);
+    /// assert_eq!(seeker.seek_follow_timeout(b"\n", interval, timeout).unwrap(), 1);
+    /// assert_eq!(seeker.seek_follow_timeout(b"\n", interval, timeout).is_err(), true);
+    /// ```
+    ///
+    /// [`seek_follow`]: #method.seek_follow
+    pub fn seek_follow_timeout(
+        &mut self,
+        bytes: &[u8],
+        interval: Duration,
+        timeout: Duration,
+    ) -> Result<u64> {
+        self.follow(bytes, interval, Some(Instant::now() + timeout))
+    }
+
+    /// Searches for the given bytes **forwards**, and returns the offset (relative to the start
+    /// of the underlying byte stream) if the given bytes were found.
+    ///
+    /// If the initialized `ByteSeeker` haven't been called before, `seek` will start from
+    /// the beginning; Otherwise, it will start from the last found `seek` position + 1.
+    ///
+    /// The `ByteSeeker` is stateful, which means you can call `seek` multiple times until
+    /// reaching the end of the underlying byte stream, or the last occurence found by `seek_back`.
+    ///
+    /// # Errors
+    ///
+    /// If the given bytes were not found, an error variant of `ErrorKind::ByteNotFound` will be
+    /// returned. If any other I/O errors were encountered, an error variant of `ErrorKind::Io`
+    /// will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = [b'0', b'\n', b'\n', b'\n'];
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// assert_eq!(seeker.seek(b"0").unwrap(), 0);
+    /// assert_eq!(seeker.seek(b"\n\n").unwrap(), 1);
+    /// assert_eq!(seeker.seek(b"\n\n").is_err(), true);
+    /// ```
+    pub fn seek(&mut self, bytes: &[u8]) -> Result<u64> {
+        self.buf_seek(bytes, Dir::Start)
+    }
+
+    /// Searches for the given bytes **backwards**, and returns the offset (relative to the start
+    /// of the underlying byte stream) if the given bytes were found.
+    ///
+    /// If the initialized `ByteSeeker` haven't been called before, `seek` will start from
+    /// the end; Otherwise, it will start from the last found `seek` position - 1.
+    ///
+    /// The `ByteSeeker` is stateful, which means you can call `seek_back` multiple times until
+    /// reaching the start of the underlying byte stream, or the last occurence found by `seek`.
+    ///
+    /// # Errors
+    ///
+    /// If the given bytes were not found, an error variant of `ErrorKind::ByteNotFound` will be
+    /// returned. If any other I/O errors were encountered, an error variant of `ErrorKind::Io`
+    /// will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = [b'0', b'\n', b'\n', b'\n'];
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// assert_eq!(seeker.seek_back(b"\n\n").unwrap(), 2);
+    /// assert_eq!(seeker.seek_back(b"\n\n").is_err(), true);
+    /// ```
+    pub fn seek_back(&mut self, bytes: &[u8]) -> Result<u64> {
+        self.buf_seek(bytes, Dir::End)
+    }
+
+    /// Searches for the byte slice of the given [`Finder`] **forwards**, and returns the offset
+    /// (relative to the start of the underlying byte stream) if it was found.
+    ///
+    /// This is equivalent to `seek(finder.needle())`, except that the byte slice is not prepared
+    /// again on each call.
+    ///
+    /// # Errors
+    ///
+    /// See [`seek`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::{ByteSeeker, Finder};
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = [b'0', b'\n', b'\n', b'\n'];
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// let finder = Finder::new(b"\n\n");
+    /// assert_eq!(seeker.seek_with(&finder).unwrap(), 1);
+    /// assert_eq!(seeker.seek_with(&finder).is_err(), true);
+    /// ```
+    ///
+    /// [`Finder`]: struct.Finder.html
+    /// [`seek`]: #method.seek
+    pub fn seek_with(&mut self, finder: &Finder) -> Result<u64> {
+        self.finder_seek(finder, Dir::Start, self.match_mode)
+    }
+
+    /// Searches for the byte slice of the given [`Finder`] **backwards**, and returns the offset
+    /// (relative to the start of the underlying byte stream) if it was found.
+    ///
+    /// This is equivalent to `seek_back(finder.needle())`, except that the byte slice is not
+    /// prepared again on each call.
+    ///
+    /// # Errors
+    ///
+    /// See [`seek_back`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::{ByteSeeker, Finder};
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = [b'0', b'\n', b'\n', b'\n'];
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// let finder = Finder::new(b"\n\n");
+    /// assert_eq!(seeker.seek_back_with(&finder).unwrap(), 2);
+    /// assert_eq!(seeker.seek_back_with(&finder).is_err(), true);
+    /// ```
+    ///
+    /// [`Finder`]: struct.Finder.html
+    /// [`seek_back`]: #method.seek_back
+    pub fn seek_back_with(&mut self, finder: &Finder) -> Result<u64> {
+        self.finder_seek(finder, Dir::End, self.match_mode)
+    }
+
+    /// Searches for the given bytes **forwards**, starting at the given offset (relative to the
+    /// start of the underlying byte stream) instead of where the last forward search has stopped.
+    ///
+    /// This is equivalent to calling [`set_position`] followed by [`seek`]. The cursor of
+    /// backward searches is left untouched, so occurences after it are still not found.
+    ///
+    /// # Errors
+    ///
+    /// See [`seek`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"0\n1\n2\n";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// assert_eq!(seeker.seek_from(2, b"\n").unwrap(), 3);
+    /// assert_eq!(seeker.seek(b"\n").unwrap(), 5);
+    /// ```
+    ///
+    /// [`set_position`]: #method.set_position
+    /// [`seek`]: #method.seek
+    pub fn seek_from(&mut self, pos: u64, bytes: &[u8]) -> Result<u64> {
+        self.set_position(pos);
+        self.seek(bytes)
+    }
+
+    /// Searches for the given bytes **backwards**, ending at the given offset (relative to the
+    /// start of the underlying byte stream) instead of where the last backward search has
+    /// stopped.
+    ///
+    /// This is equivalent to calling [`set_back_position`] followed by [`seek_back`]. The cursor
+    /// of forward searches is left untouched, so occurences before it are still not found.
+    ///
+    /// # Errors
+    ///
+    /// See [`seek_back`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"0\n1\n2\n";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// assert_eq!(seeker.seek_back_from(4, b"\n").unwrap(), 3);
+    /// assert_eq!(seeker.seek_back(b"\n").unwrap(), 1);
+    /// ```
+    ///
+    /// [`set_back_position`]: #method.set_back_position
+    /// [`seek_back`]: #method.seek_back
+    pub fn seek_back_from(&mut self, pos: u64, bytes: &[u8]) -> Result<u64> {
+        self.set_back_position(pos);
+        self.seek_back(bytes)
+    }
+
+    /// Searches for the given masked pattern **forwards**, and returns the offset (relative to
+    /// the start of the underlying byte stream) if the pattern was found.
+    ///
+    /// Like `seek`, `seek_masked` shares the state of this `ByteSeeker`, and starts from where
+    /// the last forward search has stopped. ASCII case-insensitivity does not apply to masked
+    /// patterns.
+    ///
+    /// # Errors
+    ///
+    /// See [`seek`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::{ByteSeeker, MaskedPattern};
+    /// use std::io::Cursor;
+    ///
+    /// let pattern = MaskedPattern::from_hex("50 4B ?? ?? 14 00").unwrap();
+    ///
+    /// let bytes = b"PK\x03\x04\x14\x00 PK\x01\x02\x14\x00";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    /// assert_eq!(seeker.seek_masked(&pattern).unwrap(), 0);
+    /// assert_eq!(seeker.seek_masked(&pattern).unwrap(), 7);
+    /// assert_eq!(seeker.seek_masked(&pattern).is_err(), true);
+    /// ```
+    ///
+    /// [`seek`]: #method.seek
+    pub fn seek_masked(&mut self, pattern: &MaskedPattern) -> Result<u64> {
+        self.masked_seek(pattern, Dir::Start)
+    }
+
+    /// Searches for the given masked pattern **backwards**, and returns the offset (relative to
+    /// the start of the underlying byte stream) if the pattern was found.
+    ///
+    /// Like `seek_back`, `seek_masked_back` shares the state of this `ByteSeeker`, and starts
+    /// from where the last backward search has stopped.
+    ///
+    /// # Errors
+    ///
+    /// See [`seek`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::{ByteSeeker, MaskedPattern};
+    /// use std::io::Cursor;
+    ///
+    /// let pattern = MaskedPattern::from_hex("50 4B ?? ?? 14 00").unwrap();
+    ///
+    /// let bytes = b"PK\x03\x04\x14\x00 PK\x01\x02\x14\x00";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    /// assert_eq!(seeker.seek_masked_back(&pattern).unwrap(), 7);
+    /// assert_eq!(seeker.seek_masked_back(&pattern).unwrap(), 0);
+    /// ```
+    ///
+    /// [`seek`]: #method.seek
+    pub fn seek_masked_back(&mut self, pattern: &MaskedPattern) -> Result<u64> {
+        self.masked_seek(pattern, Dir::End)
+    }
+
+    /// Searches for the given regular expression **forwards**, and returns the first [`Match`].
+    ///
+    /// Like `seek`, `seek_regex` shares the state of this `ByteSeeker`, and starts from where the
+    /// last forward search has stopped. Empty matches are skipped.
+    ///
+    /// Since the underlying byte stream is read chunk by chunk, matches are assumed to be no
+    /// longer than the [`capacity`] of this `ByteSeeker`: longer matches may be cut short or
+    /// missed. For the same reason, the regular expression only sees the bytes searched at a
+    /// time, as if nothing preceded or followed them: anchors such as `^` and `\b` match right at
+    /// the cursor a forward search starts from, whatever byte precedes it, just as `$` matches
+    /// at the cursor a backward search starts from. They may also match where a chunk of the
+    /// byte stream begins or ends. ASCII case-insensitivity does not apply to regular
+    /// expressions; use the `(?i)` flag instead.
+    ///
+    /// This method is only available with the `regex` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity of this `ByteSeeker` is zero, an error variant of
+    /// `ErrorKind::UnsupportedLength` will be returned.
+    ///
+    /// If no match was found, an error variant of `ErrorKind::ByteNotFound` will be returned.
+    ///
+    /// If an I/O error occurs while reading the underlying byte stream, an error variant of
+    /// `ErrorKind::Io` will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use regex::bytes::Regex;
+    /// use std::io::Cursor;
+    ///
+    /// let re = Regex::new(r"id=\d+").unwrap();
+    ///
+    /// let bytes = b"id=1, id=23, id=456";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    /// assert_eq!(seeker.seek_regex(&re).unwrap().range(), 0..4);
+    /// assert_eq!(seeker.seek_regex(&re).unwrap().range(), 6..11);
+    /// assert_eq!(seeker.seek_regex(&re).unwrap().range(), 13..19);
+    /// assert_eq!(seeker.seek_regex(&re).is_err(), true);
+    /// ```
+    ///
+    /// [`Match`]: struct.Match.html
+    /// [`capacity`]: #method.capacity
+    #[cfg(feature = "regex")]
+    pub fn seek_regex(&mut self, re: &Regex) -> Result<Match> {
+        self.regex_seek(re, Dir::Start)
+    }
+
+    /// Searches for the given regular expression **backwards**, and returns the last [`Match`].
+    ///
+    /// If several matches overlap, the one that ends last is returned, so a repetition like
+    /// `\d+` matches all the trailing digits rather than just the last one. As with
+    /// [`seek_regex`], only the match the regular expression prefers at each offset counts: `a|ab`
+    /// matches `a` alone, so put the longer alternative first, as in `ab|a`, to match it.
+    ///
+    /// Like `seek_back`, `seek_regex_back` shares the state of this `ByteSeeker`, and starts from
+    /// where the last backward search has stopped. See [`seek_regex`] for the limitations on the
+    /// length of matches.
+    ///
+    /// This method is only available with the `regex` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// See [`seek_regex`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use regex::bytes::Regex;
+    /// use std::io::Cursor;
+    ///
+    /// let re = Regex::new(r"id=\d+").unwrap();
+    ///
+    /// let bytes = b"id=1, id=23, id=456";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    /// assert_eq!(seeker.seek_regex_back(&re).unwrap().range(), 13..19);
+    /// assert_eq!(seeker.seek_regex_back(&re).unwrap().range(), 6..11);
+    /// assert_eq!(seeker.seek_regex_back(&re).unwrap().range(), 0..4);
+    /// ```
+    ///
+    /// [`Match`]: struct.Match.html
+    /// [`seek_regex`]: #method.seek_regex
+    #[cfg(feature = "regex")]
+    pub fn seek_regex_back(&mut self, re: &Regex) -> Result<Match> {
+        self.regex_seek(re, Dir::End)
+    }
+
+    /// Seeks the nth occurence of the given bytes **forwards**, and returns the offset (relative
+    /// to the start of the underlying byte stream) if the given bytes were found.
+    ///
+    /// If the initialized `ByteSeeker` haven't been called before, `seek_nth`
+    /// will start from the beginning; Otherwise, it will start from the last found `seek`
+    /// position + 1.
+    ///
+    /// The `ByteSeeker` is stateful, which means you can call `seek_nth` multiple times until
+    /// reaching the end of the underlying byte stream.
+    ///
+    /// # Errors
+    ///
+    /// If the given bytes were not found, an error variant of `ErrorKind::ByteNotFound` will be
+    /// returned. If any other I/O errors were encountered, an error variant of `ErrorKind::Io`
+    /// will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use byteseeker::ByteSeeker;
+    ///
+    /// let mut bytes = [b'\n', b'\n', b'\n', b'\n', b'\n'];
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    /// assert_eq!(seeker.seek_nth(b"\n\n", 2).unwrap(), 2);
+    /// assert_eq!(seeker.seek_nth(b"\n\n", 2).is_err(), true);
+    /// ```
+    pub fn seek_nth(&mut self, bytes: &[u8], nth: usize) -> Result<u64> {
+        let mut counter = nth;
+        loop {
+            let pos = self.seek(bytes)?;
+            match counter.checked_sub(1) {
+                None => {
+                    return Err(Error::new(ErrorKind::ByteNotFound));
+                }
+                Some(0) => {
+                    return Ok(pos);
+                }
+                Some(n) => {
+                    counter = n;
+                }
+            }
+        }
+    }
+
+    /// Seeks the nth occurence of the given bytes **backwards**, and returns the offset (relative
+    /// to the start of the underlying byte stream) if the given bytes were found.
+    ///
+    /// If the initialized `ByteSeeker` haven't been called before, `seek_nth_back`
+    /// will start from the end; Otherwise, it will start from the last found `seek` position - 1.
+    ///
+    /// The `ByteSeeker` is stateful, which means you can call `seek_nth_back` multiple times until
+    /// reaching the end of the underlying byte stream.
+    ///
+    /// # Errors
+    ///
+    /// If the given bytes were not found, an error variant of `ErrorKind::ByteNotFound` will be
+    /// returned. If any other I/O errors were encountered, an error variant of `ErrorKind::Io`
+    /// will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use byteseeker::ByteSeeker;
+    ///
+    /// let mut bytes = [b'\n', b'\n', b'\n', b'\n', b'\n'];
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    /// assert_eq!(seeker.seek_nth_back(b"\n\n", 2).unwrap(), 1);
+    /// assert_eq!(seeker.seek_nth_back(b"\n\n", 2).is_err(), true);
+    /// ```
+    pub fn seek_nth_back(&mut self, bytes: &[u8], nth: usize) -> Result<u64> {
+        let mut counter = nth;
+        loop {
+            let pos = self.seek_back(bytes)?;
+            match counter.checked_sub(1) {
+                None => {
+                    return Err(Error::new(ErrorKind::ByteNotFound));
+                }
+                Some(0) => {
+                    return Ok(pos);
+                }
+                Some(n) => {
+                    counter = n;
+                }
+            }
+        }
+    }
+
+    /// Searches for any of the given byte slices **forwards**, and returns the found [`Match`],
+    /// whose `pattern_id` is the index of the found byte slice within `patterns`.
+    ///
+    /// If several byte slices occur, the one that starts first is returned; if several of them
+    /// start at the same offset, the one that comes first in `patterns` is returned.
+    ///
+    /// The given byte slices are compiled into an automaton, which is reused by subsequent calls
+    /// with the same byte slices. Like `seek`, `seek_any` shares the state of this `ByteSeeker`,
+    /// and starts from where the last forward search has stopped.
+    ///
+    /// # Errors
+    ///
+    /// If `patterns` is empty, or any of its byte slices is invalid, an error variant of
+    /// `ErrorKind::UnsupportedLength` will be returned. If none of the given byte slices were
+    /// found, an error variant of `ErrorKind::ByteNotFound` will be returned. If any other I/O
+    /// errors were encountered, an error variant of `ErrorKind::Io` will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::{ByteSeeker, Match};
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"0\n1\r\n2\x1e";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// let patterns: &[&[u8]] = &[b"\r\n", b"\n", b"\x1e"];
+    /// for &(pattern_id, start, end) in &[(1, 1, 2), (0, 3, 5), (2, 6, 7)] {
+    ///     let m = seeker.seek_any(patterns).unwrap();
+    ///     assert_eq!(m, Match { start, end, pattern_id });
+    /// }
+    /// assert_eq!(seeker.seek_any(patterns).is_err(), true);
+    /// ```
+    ///
+    /// [`Match`]: struct.Match.html
+    pub fn seek_any(&mut self, patterns: &[&[u8]]) -> Result<Match> {
+        self.any_seek(patterns, Dir::Start)
+    }
+
+    /// Searches for any of the given byte slices **backwards**, and returns the found [`Match`],
+    /// whose `pattern_id` is the index of the found byte slice within `patterns`.
+    ///
+    /// If several byte slices occur, the one that ends last is returned; if several of them end
+    /// at the same offset, the one that comes first in `patterns` is returned.
+    ///
+    /// Like `seek_back`, `seek_any_back` shares the state of this `ByteSeeker`, and starts from
+    /// where the last backward search has stopped.
+    ///
+    /// # Errors
+    ///
+    /// See [`seek_any`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::{ByteSeeker, Match};
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"0\n1\r\n2\x1e";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// let patterns: &[&[u8]] = &[b"\r\n", b"\n", b"\x1e"];
+    /// for &(pattern_id, start, end) in &[(2, 6, 7), (0, 3, 5), (1, 1, 2)] {
+    ///     let m = seeker.seek_any_back(patterns).unwrap();
+    ///     assert_eq!(m, Match { start, end, pattern_id });
+    /// }
+    /// assert_eq!(seeker.seek_any_back(patterns).is_err(), true);
+    /// ```
+    ///
+    /// [`Match`]: struct.Match.html
+    /// [`seek_any`]: #method.seek_any
+    pub fn seek_any_back(&mut self, patterns: &[&[u8]]) -> Result<Match> {
+        self.any_seek(patterns, Dir::End)
+    }
+
+    /// Searches for the given bytes **forwards** like `seek`, appends the bytes from where the
+    /// search started up to (but excluding) the found occurence to `buf`, and returns the offset
+    /// of the found occurence.
+    ///
+    /// Bytes still held in the internal buffer after the search are not read again from the
+    /// underlying byte stream. Nothing is appended to `buf` if the given bytes were not found.
+    ///
+    /// # Errors
+    ///
+    /// See [`seek`]. The state of this `ByteSeeker` is left untouched if an error occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"0\n1\n2";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// let mut buf = Vec::new();
+    /// assert_eq!(seeker.read_until_match(b"\n", &mut buf).unwrap(), 1);
+    /// assert_eq!(seeker.read_until_match(b"\n", &mut buf).unwrap(), 3);
+    /// assert_eq!(buf, b"01");
+    /// assert_eq!(seeker.read_until_match(b"\n", &mut buf).is_err(), true);
+    /// ```
+    ///
+    /// [`seek`]: #method.seek
+    pub fn read_until_match(&mut self, bytes: &[u8], buf: &mut Vec<u8>) -> Result<u64> {
+        self.finder_read(&self.finder(bytes), Dir::Start, buf)
+    }
+
+    /// Searches **forwards** for `start` and then for `end` after it, appends the bytes between
+    /// the two found occurences to `buf`, and returns the offset of the first appended byte.
+    ///
+    /// The next forward search starts right after the found occurence of `end`. Bytes still
+    /// held in the internal buffer after the searches are not read again from the underlying
+    /// byte stream.
+    ///
+    /// # Errors
+    ///
+    /// See [`seek`]. An error variant of `ErrorKind::ByteNotFound` is returned if either `start`
+    /// or `end` was not found, and the state of this `ByteSeeker` is left untouched if an error
+    /// occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"<a><b>";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// let mut buf = Vec::new();
+    /// assert_eq!(seeker.read_between(b"<", b">", &mut buf).unwrap(), 1);
+    /// assert_eq!(buf, b"a");
+    /// assert_eq!(seeker.read_between(b"<", b">", &mut buf).unwrap(), 4);
+    /// assert_eq!(buf, b"ab");
+    /// assert_eq!(seeker.read_between(b"<", b">", &mut buf).is_err(), true);
+    /// ```
+    ///
+    /// [`seek`]: #method.seek
+    pub fn read_between(&mut self, start: &[u8], end: &[u8], buf: &mut Vec<u8>) -> Result<u64> {
+        let state = self.state;
+        let (start, end) = (self.finder(start), self.finder(end));
+        let result = self
+            .finder_seek(&start, Dir::Start, MatchMode::NonOverlapping)
+            .and_then(|pos| {
+                let from = pos + start.len() as u64;
+                let to = self.finder_seek(&end, Dir::Start, MatchMode::NonOverlapping)?;
+                self.read_into(from..to, buf).map(|_| from)
+            });
+        self.restore_on_err(state, result)
+    }
+
+    /// Searches for the given bytes **backwards** like `seek_back`, appends the bytes from right
+    /// after the found occurence up to where the search started to `buf`, and returns the offset
+    /// of the found occurence.
+    ///
+    /// Unless `seek_back` or the like have been called before, this reads everything after the
+    /// last occurence of the given bytes. Bytes still held in the internal buffer after the
+    /// search are not read again from the underlying byte stream.
+    ///
+    /// # Errors
+    ///
+    /// See [`seek_back`]. The state of this `ByteSeeker` is left untouched if an error occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"0\n1\n2";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// let mut buf = Vec::new();
+    /// assert_eq!(seeker.read_from_match_to_end(b"\n", &mut buf).unwrap(), 3);
+    /// assert_eq!(buf, b"2");
+    /// ```
+    ///
+    /// [`seek_back`]: #method.seek_back
+    pub fn read_from_match_to_end(&mut self, bytes: &[u8], buf: &mut Vec<u8>) -> Result<u64> {
+        self.finder_read(&self.finder(bytes), Dir::End, buf)
+    }
+
+    /// Counts the non-overlapping occurences of the given bytes between where the next forward
+    /// search starts and where the next backward search ends, which is the whole underlying byte
+    /// stream for a fresh `ByteSeeker`.
+    ///
+    /// The byte stream is scanned chunk by chunk without moving the cursors of this
+    /// `ByteSeeker`, and single bytes are counted a whole chunk at a time, which is much faster
+    /// than calling `seek` once per occurence.
+    ///
+    /// # Errors
+    ///
+    /// If the given bytes are invalid, an error variant of `ErrorKind::UnsupportedLength` will be
+    /// returned. If any I/O errors were encountered, an error variant of `ErrorKind::Io` will be
+    /// returned. Finding no occurences is not an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"0\n\n\n1\n";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// assert_eq!(seeker.count(b"\n").unwrap(), 4);
+    /// assert_eq!(seeker.count(b"\n\n").unwrap(), 1);
+    /// assert_eq!(seeker.count(b"\r\n").unwrap(), 0);
+    /// ```
+    pub fn count(&mut self, bytes: &[u8]) -> Result<u64> {
+        self.count_matches(&self.finder(bytes), false)
+    }
+
+    /// Like [`count`], but also counts the occurences that overlap each other.
+    ///
+    /// # Errors
+    ///
+    /// See [`count`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"0\n\n\n1\n";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// assert_eq!(seeker.count_overlapping(b"\n\n").unwrap(), 2);
+    /// ```
+    ///
+    /// [`count`]: #method.count
+    pub fn count_overlapping(&mut self, bytes: &[u8]) -> Result<u64> {
+        self.count_matches(&self.finder(bytes), true)
+    }
+
+    /// Returns an iterator over the offsets (relative to the start of the underlying byte stream)
+    /// of the occurences of the given bytes, searching **forwards**.
+    ///
+    /// The iterator is built on [`seek`], and continues from where the previous searches of this
+    /// `ByteSeeker` have stopped. Reaching the end of the underlying byte stream terminates the
+    /// iterator rather than yielding an error of `ErrorKind::ByteNotFound`.
+    ///
+    /// The returned iterator is double-ended: calling `next_back` searches backwards using
+    /// [`seek_back`], and the two ends meet in the middle.
+    ///
+    /// # Errors
+    ///
+    /// If the given bytes are invalid, an error variant of `ErrorKind::UnsupportedLength` will be
+    /// yielded. If any I/O errors were encountered, an error variant of `ErrorKind::Io` will be
+    /// yielded. The iterator is finished after yielding an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"0\n1\n2\n";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// let offsets: Vec<u64> = seeker.find_iter(b"\n").map(|x| x.unwrap()).collect();
+    /// assert_eq!(offsets, vec![1, 3, 5]);
+    /// ```
+    ///
+    /// [`seek`]: #method.seek
+    /// [`seek_back`]: #method.seek_back
+    pub fn find_iter(&mut self, bytes: &[u8]) -> FindIter<'_, R> {
+        FindIter::new(self, self.finder(bytes))
+    }
+
+    /// Returns an iterator over the offsets (relative to the start of the underlying byte stream)
+    /// of the occurences of the given bytes, searching **backwards**.
+    ///
+    /// This is the reverse of [`find_iter`], which is built on [`seek_back`] and continues from
+    /// where the previous backward searches of this `ByteSeeker` have stopped.
+    ///
+    /// # Errors
+    ///
+    /// See [`find_iter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"0\n1\n2\n";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// let offsets: Vec<u64> = seeker.rfind_iter(b"\n").map(|x| x.unwrap()).collect();
+    /// assert_eq!(offsets, vec![5, 3, 1]);
+    /// ```
+    ///
+    /// [`find_iter`]: #method.find_iter
+    /// [`seek_back`]: #method.seek_back
+    pub fn rfind_iter(&mut self, bytes: &[u8]) -> Rev<FindIter<'_, R>> {
+        FindIter::new(self, self.finder(bytes)).rev()
+    }
+
+    /// Returns an iterator over the records of the underlying byte stream separated by the given
+    /// delimiter, which yields the offset (relative to the start of the underlying byte stream)
+    /// and the contents of each record, searching **forwards**.
+    ///
+    /// Like `BufRead::split`, the delimiters are not part of the yielded contents, and a
+    /// delimiter at the very end does not start an empty last record. Unlike it, the delimiter
+    /// can be several bytes long.
+    ///
+    /// The iterator is built on [`seek`], and continues from where the previous forward searches
+    /// of this `ByteSeeker` have stopped.
+    ///
+    /// # Errors
+    ///
+    /// If the given delimiter is invalid, an error variant of `ErrorKind::UnsupportedLength` will
+    /// be yielded. If any I/O errors were encountered, an error variant of `ErrorKind::Io` will
+    /// be yielded. The iterator is finished after yielding an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"a\0\0END\0b\0\0END\0c";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// let records: Vec<(u64, Vec<u8>)> = seeker
+    ///     .split(b"\0\0END\0")
+    ///     .map(|x| x.unwrap())
+    ///     .collect();
+    /// assert_eq!(
+    ///     records,
+    ///     vec![(0, b"a".to_vec()), (7, b"b".to_vec()), (14, b"c".to_vec())]
+    /// );
+    /// ```
+    ///
+    /// [`seek`]: #method.seek
+    pub fn split(&mut self, delimiter: &[u8]) -> Split<'_, R> {
+        Split::new(self, self.finder(delimiter), Dir::Start)
+    }
+
+    /// Returns an iterator over the records of the underlying byte stream separated by the given
+    /// delimiter, which yields the offset (relative to the start of the underlying byte stream)
+    /// and the contents of each record, searching **backwards**.
+    ///
+    /// Delimiters are matched from the end, so for a delimiter that can overlap itself, such as
+    /// `aa`, the records may differ from the ones yielded by [`split`]: on `abaaaaaba` the
+    /// matches are at 5 and 3 rather than at 2 and 4. For any other delimiter the records are the
+    /// ones yielded by [`split`], in reverse order.
+    ///
+    /// The iterator is built on [`seek_back`], and continues from where the previous backward
+    /// searches of this `ByteSeeker` have stopped.
+    ///
+    /// # Errors
+    ///
+    /// See [`split`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b",0,,1,";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// let records: Vec<(u64, Vec<u8>)> = seeker.rsplit(b",").map(|x| x.unwrap()).collect();
+    /// assert_eq!(
+    ///     records,
+    ///     vec![
+    ///         (4, b"1".to_vec()),
+    ///         (3, b"".to_vec()),
+    ///         (1, b"0".to_vec()),
+    ///         (0, b"".to_vec()),
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// [`split`]: #method.split
+    /// [`seek_back`]: #method.seek_back
+    pub fn rsplit(&mut self, delimiter: &[u8]) -> Split<'_, R> {
+        Split::new(self, self.finder(delimiter), Dir::End)
+    }
+
+    /// Returns an iterator over the lines of the underlying byte stream, starting from the last
+    /// one, which yields the offset (relative to the start of the underlying byte stream) and the
+    /// contents of each line.
+    ///
+    /// Lines are terminated by either `\n` or `\r\n`, which are not part of the yielded
+    /// contents. A final newline does not start an empty last line, and an empty byte stream has
+    /// no lines at all. A `\r` at the very end, with no `\n` after it, is kept in the last line.
+    ///
+    /// The iterator is built on [`seek_back`], and continues from where the previous backward
+    /// searches of this `ByteSeeker` have stopped: the bytes before the back cursor are treated
+    /// as if they were the whole byte stream.
+    ///
+    /// # Errors
+    ///
+    /// If any I/O errors were encountered, an error variant of `ErrorKind::Io` will be yielded.
+    /// The iterator is finished after yielding an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"0\r\n\n2\n";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// let lines: Vec<(u64, Vec<u8>)> = seeker.lines_back().map(|x| x.unwrap()).collect();
+    /// assert_eq!(lines, vec![(4, b"2".to_vec()), (3, b"".to_vec()), (0, b"0".to_vec())]);
+    /// ```
+    ///
+    /// [`seek_back`]: #method.seek_back
+    pub fn lines_back(&mut self) -> LinesBack<'_, R> {
+        LinesBack::new(self)
+    }
+
+    /// Returns the last `n` lines of the underlying byte stream, in their original order, along
+    /// with their offsets (relative to the start of the underlying byte stream).
+    ///
+    /// Fewer lines are returned if the byte stream does not have that many. See [`lines_back`]
+    /// for how lines are delimited.
+    ///
+    /// # Errors
+    ///
+    /// If any I/O errors were encountered, an error variant of `ErrorKind::Io` will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = b"0\n1\n2\n";
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    ///
+    /// let lines = seeker.tail(2).unwrap();
+    /// assert_eq!(lines, vec![(2, b"1".to_vec()), (4, b"2".to_vec())]);
+    /// ```
+    ///
+    /// [`lines_back`]: #method.lines_back
+    pub fn tail(&mut self, n: usize) -> Result<Vec<(u64, Vec<u8>)>> {
+        let mut lines = self.lines_back().take(n).collect::<Result<Vec<_>>>()?;
+        lines.reverse();
+        Ok(lines)
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader. The position of the
+    /// underlying reader after a search is unspecified, so seek to the desired offset first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::{Cursor, Read, Seek, SeekFrom};
+    ///
+    /// let bytes = [b'0', b'\n', b'\n', b'\n'];
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut seeker = ByteSeeker::new(&mut cursor);
+    /// assert_eq!(seeker.seek(b"\n").unwrap(), 1);
+    ///
+    /// let mut reader = seeker.get_mut();
+    /// reader.seek(SeekFrom::Start(2)).unwrap();
     /// let mut buf = Vec::new();
     /// let _ = reader.read_to_end(&mut buf);
     /// assert_eq!(&buf, &[b'\n', b'\n']);
     /// ```
-    pub fn get_mut(&mut self) -> &mut RS {
+    pub fn get_mut(&mut self) -> &mut R {
+        // The caller may move or modify the stream, so nothing read so far can be trusted.
+        self.stream_pos = None;
+        self.window.clear();
+        &mut self.inner
+    }
+
+    /// Gets a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = [b'0', b'\n', b'\n', b'\n'];
+    /// let seeker = ByteSeeker::new(Cursor::new(bytes));
+    /// assert_eq!(seeker.get_ref().get_ref(), &bytes);
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Unwraps this `ByteSeeker`, returning the underlying reader.
+    ///
+    /// The position of the underlying reader is unspecified, so seek to the desired offset before
+    /// reading from it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use byteseeker::ByteSeeker;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes = [b'0', b'\n', b'\n', b'\n'];
+    /// let mut seeker = ByteSeeker::new(Cursor::new(bytes));
+    /// assert_eq!(seeker.seek(b"\n").unwrap(), 1);
+    ///
+    /// let cursor = seeker.into_inner();
+    /// assert_eq!(cursor.into_inner(), bytes);
+    /// ```
+    pub fn into_inner(self) -> R {
         self.inner
     }
 }
 
-impl<'a, RS: 'a + Read + Seek> ByteSeeker<'a, RS> {
-    fn buf_seek(&mut self, bytes: &[u8], dir: Dir) -> Result<usize> {
-        if self.state.done {
-            return Err(Error::new(ErrorKind::ByteNotFound));
+impl<R: Read + Seek> ByteSeeker<R> {
+    // Prepares the given bytes for searching, according to the options of this `ByteSeeker`.
+    fn finder(&self, bytes: &[u8]) -> Finder {
+        if self.ignore_case {
+            Finder::new_ascii_case_insensitive(bytes)
+        } else {
+            Finder::new(bytes)
+        }
+    }
+
+    // Seeks forwards, waiting for the underlying stream to grow until the given bytes were found
+    // or the deadline, if any, has passed.
+    fn follow(
+        &mut self,
+        bytes: &[u8],
+        interval: Duration,
+        deadline: Option<Instant>,
+    ) -> Result<u64> {
+        let finder = self.finder(bytes);
+        loop {
+            // The backward cursor would hide the appended bytes.
+            self.state.rpos = self.range.end;
+            match self.finder_seek(&finder, Dir::Start, self.match_mode) {
+                Err(ref e) if matches!(e.kind(), ErrorKind::ByteNotFound) => {}
+                result => return result,
+            }
+
+            // Only search again once the range changed, which includes the stream being
+            // truncated.
+            let end = self.range.end;
+            while {
+                self.refresh()?;
+                self.range.end == end
+            } {
+                let wait = if end == self.range_end {
+                    // Nothing appended to the stream would fall within the range.
+                    None
+                } else {
+                    match deadline {
+                        Some(deadline) => deadline.checked_duration_since(Instant::now()),
+                        None => Some(interval),
+                    }
+                };
+                match wait {
+                    Some(wait) if wait > Duration::from_secs(0) => {
+                        thread::sleep(interval.min(wait))
+                    }
+                    _ => return Err(Error::new(ErrorKind::ByteNotFound)),
+                }
+            }
         }
+    }
+
+    fn buf_seek(&mut self, bytes: &[u8], dir: Dir) -> Result<u64> {
+        // Building the shift tables outweighs a short search, so reuse them as long as the needle
+        // does not change.
+        let finder = match self.byte_finder.take() {
+            Some(finder)
+                if finder.needle() == bytes
+                    && finder.is_ascii_case_insensitive() == self.ignore_case =>
+            {
+                finder
+            }
+            _ => self.finder(bytes),
+        };
+        let found = self.finder_seek(&finder, dir, self.match_mode);
+        self.byte_finder = Some(finder);
+        found
+    }
 
-        let bytes_len = bytes.len();
+    pub(crate) fn finder_seek(
+        &mut self,
+        finder: &Finder,
+        dir: Dir,
+        mode: MatchMode,
+    ) -> Result<u64> {
+        let bytes_len = finder.len();
         if bytes_len == 0 || bytes_len > self.cap {
             return Err(Error::new(ErrorKind::UnsupportedLength));
         }
 
-        use std::cmp::Ordering::*;
-        match self.len.cmp(&bytes_len) {
-            Less => {
-                self.state.done = true;
-                return Err(Error::new(ErrorKind::ByteNotFound));
+        Ok(self.search(finder, dir, mode)?.start)
+    }
+
+    // Seeks in the given direction, and appends the bytes between the cursor of that direction
+    // and the found match to `buf`.
+    pub(crate) fn finder_read(
+        &mut self,
+        finder: &Finder,
+        dir: Dir,
+        buf: &mut Vec<u8>,
+    ) -> Result<u64> {
+        let state = self.state;
+        // The found match is consumed along with the bytes before it.
+        let result = self
+            .finder_seek(finder, dir, MatchMode::NonOverlapping)
+            .and_then(|pos| {
+                let range = match dir {
+                    Dir::Start => state.lpos..pos,
+                    Dir::End => pos + finder.len() as u64..state.rpos,
+                };
+                self.read_into(range, buf).map(|_| pos)
+            });
+        self.restore_on_err(state, result)
+    }
+
+    fn masked_seek(&mut self, pattern: &MaskedPattern, dir: Dir) -> Result<u64> {
+        if pattern.is_empty() || pattern.len() > self.cap {
+            return Err(Error::new(ErrorKind::UnsupportedLength));
+        }
+
+        Ok(self.search(pattern, dir, self.match_mode)?.start)
+    }
+
+    #[cfg(feature = "regex")]
+    fn regex_seek(&mut self, re: &Regex, dir: Dir) -> Result<Match> {
+        // Matches are assumed to be no longer than the capacity, so none would fit.
+        if self.cap == 0 {
+            return Err(Error::new(ErrorKind::UnsupportedLength));
+        }
+
+        // No match can be longer than the range, however large the capacity.
+        let range_len = usize::try_from(self.range.end - self.range.start).unwrap_or(usize::MAX);
+        let max_len = self.cap.min(range_len.max(1));
+        self.search(&RegexSearch::new(re, max_len), dir, self.match_mode)
+    }
+
+    fn any_seek(&mut self, patterns: &[&[u8]], dir: Dir) -> Result<Match> {
+        if patterns.is_empty()
+            || patterns
+                .iter()
+                .any(|pattern| pattern.is_empty() || pattern.len() > self.cap)
+        {
+            return Err(Error::new(ErrorKind::UnsupportedLength));
+        }
+
+        // Building the automaton is costly, so reuse it as long as the patterns do not change.
+        let finder = match self.multi_finder.take() {
+            Some(finder) if finder.is_built_from(patterns, self.ignore_case) => finder,
+            _ => MultiFinder::new(patterns, self.ignore_case),
+        };
+        let found = self.search(&finder, dir, self.match_mode);
+        self.multi_finder = Some(finder);
+        found
+    }
+
+    // Measures the length of the underlying stream.
+    fn measure(&mut self) -> Result<u64> {
+        self.stream_pos = None;
+        let len = self.inner.seek(SeekFrom::End(0))?;
+        self.stream_pos = Some(len);
+        Ok(len)
+    }
+
+    // Updates the length of the underlying stream, growing or shrinking the range and the
+    // cursors along with it.
+    fn set_len(&mut self, len: u64) {
+        if len < self.len {
+            // The stream was truncated, so the bytes read so far may be stale.
+            self.window.clear();
+        }
+        let end = self.range_end.min(len);
+        if self.state.rpos == self.range.end {
+            self.state.rpos = end;
+        }
+        self.len = len;
+        self.range = self.range.start.min(end)..end;
+        self.state.lpos = self.state.lpos.min(end);
+        self.state.rpos = self.state.rpos.min(end);
+    }
+
+    // Returns the actual length of the underlying stream if `e` was caused by the stream ending
+    // earlier than expected, because its length changed.
+    fn changed_len(&mut self, e: &Error) -> Result<Option<u64>> {
+        match e.kind() {
+            ErrorKind::Io(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                let actual = self.measure()?;
+                Ok(Some(actual).filter(|&actual| actual != self.len))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    // Searches in the given direction between the two cursors, and advances the cursor of that
+    // direction.
+    fn search<S: Search>(&mut self, searcher: &S, dir: Dir, mode: MatchMode) -> Result<Match> {
+        let limit = self.range.end - self.range.start;
+        self.window
+            .prepare(self.chunk_size, searcher.max_len(), limit);
+        let found = loop {
+            let (start, end) = (self.state.lpos, self.state.rpos);
+            let result = match dir {
+                Dir::Start => self.find_forward(searcher, start, end),
+                Dir::End => self.find_backward(searcher, start, end),
+            };
+            match result {
+                Ok(found) => break found,
+                Err(e) => self.recover(e)?,
+            }
+        };
+
+        match found {
+            Some(m) => {
+                self.state.advance(&m, dir, mode);
+                Ok(m)
+            }
+            None => Err(Error::new(ErrorKind::ByteNotFound)),
+        }
+    }
+
+    // Counts the matches of `finder` between the two cursors, without moving them.
+    fn count_matches(&mut self, finder: &Finder, overlapping: bool) -> Result<u64> {
+        let len = finder.len();
+        if len == 0 || len > self.cap {
+            return Err(Error::new(ErrorKind::UnsupportedLength));
+        }
+
+        let limit = self.range.end - self.range.start;
+        self.window.prepare(self.chunk_size, len, limit);
+        loop {
+            let (start, end) = (self.state.lpos, self.state.rpos);
+            let result = if len == 1 {
+                self.count_byte(finder, start, end)
+            } else {
+                self.count_forward(finder, overlapping, start, end)
+            };
+            match result {
+                Ok(count) => return Ok(count),
+                Err(e) => self.recover(e)?,
             }
-            Equal => {
-                self.state.done = true;
-                let mut buf = vecu8(bytes_len);
-                self.inner.read_exact(&mut buf)?;
-                if bytes == &buf {
-                    return Ok(0);
-                } else {
-                    return Err(Error::new(ErrorKind::ByteNotFound));
-                }
+        }
+    }
+
+    // Counts the occurences of the single byte needle of `finder` within `start..end`, a whole
+    // window at a time.
+    fn count_byte(&mut self, finder: &Finder, mut start: u64, end: u64) -> Result<u64> {
+        let byte = finder.needle()[0];
+        let mut count = 0;
+        while start < end {
+            if start < self.window.pos || self.window.end() <= start {
+                self.fill_forward(start)?;
             }
-            Greater => match dir {
-                Dir::Start => loop {
-                    if self.state.done {
-                        return Err(Error::new(ErrorKind::ByteNotFound));
-                    }
+            let hi = self.window.end().min(end);
+            let window = self.window.bytes(start..hi);
+            count += window.iter().filter(|&&b| finder.eq_byte(b, byte)).count() as u64;
+            start = hi;
+        }
+        Ok(count)
+    }
+
+    // Counts the matches of `finder` within `start..end`, which may overlap if `overlapping`.
+    fn count_forward(
+        &mut self,
+        finder: &Finder,
+        overlapping: bool,
+        mut start: u64,
+        end: u64,
+    ) -> Result<u64> {
+        let mut count = 0;
+        while let Some(m) = self.find_forward(finder, start, end)? {
+            count += 1;
+            start = if overlapping { m.start + 1 } else { m.end };
+        }
+        Ok(count)
+    }
 
-                    let remaining = self.len - self.state.lpos;
-                    let mut buf_len = self.buf.len();
+    // Applies the change policy if `e` was caused by the length of the underlying stream having
+    // changed, and returns `Ok` if the failed operation should be retried.
+    fn recover(&mut self, e: Error) -> Result<()> {
+        // The window may have been overwritten halfway.
+        self.window.clear();
+        match self.changed_len(&e)? {
+            Some(actual) => self.change_len(actual),
+            None => Err(e),
+        }
+    }
 
-                    if remaining < bytes_len {
-                        return Err(Error::new(ErrorKind::ByteNotFound));
-                    } else if remaining < buf_len {
-                        self.buf.truncate(remaining);
-                        buf_len = remaining;
-                        self.state.last = true;
-                    }
-                    self.inner.read_exact(&mut self.buf)?;
-
-                    if let Some(pos) = self.buf.iter().position(|&x| x == bytes[0]) {
-                        let cpos = self.state.lpos + pos;
-                        if self.match_in_place(cpos, bytes)? {
-                            if cpos + bytes_len > self.len {
-                                self.state.done = true;
-                            } else {
-                                self.state.lpos = self
-                                    .inner
-                                    .seek(SeekFrom::Start((cpos + bytes_len) as u64))?
-                                    as usize;
-                            }
-                            return Ok(cpos);
-                        } else if buf_len == remaining {
-                            return Err(Error::new(ErrorKind::ByteNotFound));
-                        }
-                    } else {
-                        if self.state.last {
-                            self.state.done = true;
-                            return Err(Error::new(ErrorKind::ByteNotFound));
-                        } else {
-                            self.state.lpos = self
-                                .inner
-                                .seek(SeekFrom::Start((self.state.lpos + buf_len) as u64))?
-                                as usize;
-                        }
-                    }
-                },
-                Dir::End => loop {
-                    if self.state.done {
-                        return Err(Error::new(ErrorKind::ByteNotFound));
-                    }
+    // Applies the change policy to the actual length of the underlying stream, which differs
+    // from the expected one.
+    fn change_len(&mut self, actual: u64) -> Result<()> {
+        match self.change_policy {
+            ChangePolicy::Error => {
+                return Err(Error::new(ErrorKind::StreamChanged {
+                    expected: self.len,
+                    actual,
+                }))
+            }
+            ChangePolicy::Remeasure => self.set_len(actual),
+            ChangePolicy::Restart => {
+                self.set_len(actual);
+                self.state = State {
+                    lpos: self.range.start,
+                    rpos: self.range.end,
+                };
+            }
+        }
+        Ok(())
+    }
+
+    // Searches forwards for the first match within `start..end`, reading further chunks into the
+    // window as needed.
+    fn find_forward<S: Search>(
+        &mut self,
+        searcher: &S,
+        mut start: u64,
+        end: u64,
+    ) -> Result<Option<Match>> {
+        loop {
+            match self.window.find_forward(searcher, start, end) {
+                Step::Done(found) => return Ok(found),
+                Step::Move(pos) => {
+                    self.fill_forward(pos)?;
+                    start = pos;
+                }
+            }
+        }
+    }
 
-                    let remaining = self.state.rpos + 1;
-                    let mut buf_len = self.buf.len();
+    // Searches backwards for the last match within `start..end`, reading preceding chunks into
+    // the window as needed.
+    fn find_backward<S: Search>(
+        &mut self,
+        searcher: &S,
+        start: u64,
+        mut end: u64,
+    ) -> Result<Option<Match>> {
+        loop {
+            match self.window.find_backward(searcher, start, end) {
+                Step::Done(found) => return Ok(found),
+                Step::Move(pos) => {
+                    self.fill_backward(pos)?;
+                    end = pos;
+                }
+            }
+        }
+    }
 
-                    if remaining < bytes_len {
-                        return Err(Error::new(ErrorKind::ByteNotFound));
-                    } else if remaining < buf_len {
-                        self.buf.truncate(remaining);
-                        buf_len = remaining;
-                        self.state.last = true;
-                    }
+    // Moves the window so that it starts at `start`, and reads the bytes that follow the carried
+    // over ones.
+    fn fill_forward(&mut self, start: u64) -> Result<()> {
+        let refill = self.window.move_forward(start, self.range.end);
+        self.read_at(refill)
+    }
 
-                    self.inner
-                        .seek(SeekFrom::Start((remaining - buf_len) as u64))?
-                        as usize;
-                    self.inner.read_exact(&mut self.buf)?;
-
-                    if let Some(pos) = self
-                        .buf
-                        .iter()
-                        .rev()
-                        .position(|&x| x == bytes[bytes_len - 1])
-                    {
-                        let cpos = self.state.rpos - pos - (bytes_len - 1);
-                        if self.match_in_place(cpos, bytes)? {
-                            if (cpos as isize) - 1 < 0 {
-                                self.state.done = true;
-                            } else {
-                                self.state.rpos =
-                                    self.inner.seek(SeekFrom::Start((cpos - 1) as u64))? as usize;
-                            }
-                            return Ok(cpos);
-                        } else if buf_len == remaining {
-                            return Err(Error::new(ErrorKind::ByteNotFound));
-                        }
-                    } else {
-                        if self.state.last {
-                            self.state.done = true;
-                            return Err(Error::new(ErrorKind::ByteNotFound));
-                        } else {
-                            self.state.rpos = remaining - buf_len;
-                        }
-                    }
-                },
-            },
+    // Moves the window so that it ends at `end`, and reads the bytes that precede the carried
+    // over ones.
+    fn fill_backward(&mut self, end: u64) -> Result<()> {
+        let refill = self.window.move_backward(end, self.range.start);
+        self.read_at(refill)
+    }
+
+    // Reads the bytes requested by `refill` into the window, seeking only if the stream is not
+    // positioned at them already. The window is left empty if reading fails.
+    fn read_at(&mut self, refill: Refill) -> Result<()> {
+        let result = read_exact_at(
+            &mut self.inner,
+            &mut self.stream_pos,
+            refill.pos,
+            &mut self.window.buf[refill.range],
+        );
+        if result.is_err() {
+            self.window.clear();
         }
+        result
     }
 
-    fn match_in_place(&mut self, pos: usize, bytes: &[u8]) -> Result<bool> {
-        self.inner.seek(SeekFrom::Start(pos as u64))?;
-        let len = bytes.len();
-        if pos + len > self.len {
-            return Ok(false);
+    // Appends the bytes within `range` of the underlying stream to `out`, copying those within
+    // the window instead of reading them again.
+    pub(crate) fn read_into(&mut self, range: Range<u64>, out: &mut Vec<u8>) -> Result<()> {
+        let (mut pos, end) = (range.start, range.end);
+        while pos < end {
+            let (buf_pos, buf_end) = (self.window.pos, self.window.end());
+            let to = if buf_pos <= pos && pos < buf_end {
+                let to = end.min(buf_end);
+                out.extend_from_slice(self.window.bytes(pos..to));
+                to
+            } else {
+                // Reads up to the window if it lies ahead, so its bytes are copied afterwards.
+                let to = if pos < buf_pos { end.min(buf_pos) } else { end };
+                let len = usize::try_from(to - pos)
+                    .map_err(|_| Error::new(ErrorKind::OffsetOverflow(to - pos)))?;
+                let old_len = out.len();
+                out.resize(old_len + len, 0);
+                let result = read_exact_at(
+                    &mut self.inner,
+                    &mut self.stream_pos,
+                    pos,
+                    &mut out[old_len..],
+                );
+                if let Err(e) = result {
+                    out.truncate(old_len);
+                    return match self.changed_len(&e)? {
+                        Some(actual) => Err(Error::new(ErrorKind::StreamChanged {
+                            expected: self.len,
+                            actual,
+                        })),
+                        None => Err(e),
+                    };
+                }
+                to
+            };
+            pos = to;
         }
-        let mut buf = vecu8(len);
-        self.inner.read_exact(&mut buf)?;
-        self.inner.seek(SeekFrom::Start(pos as u64))?;
-        if &buf == bytes {
-            return Ok(true);
-        } else {
-            return Ok(false);
+        Ok(())
+    }
+
+    // Restores the given state if `result` is an error, and passes `result` through.
+    fn restore_on_err<T>(&mut self, state: State, result: Result<T>) -> Result<T> {
+        if result.is_err() {
+            self.state = state;
         }
+        result
     }
 }
 
-// Creates a `Vec<u8>` whose capacity and length are exactly the same.
-fn vecu8(len: usize) -> Vec<u8> {
-    let mut vec = Vec::with_capacity(len);
-    unsafe {
-        vec.set_len(len);
-    }
-    vec
+// Clamps the given range into `0..len`, making it empty if it is reversed.
+fn clamp_range(range: Range<u64>, len: u64) -> Range<u64> {
+    let end = range.end.min(len);
+    range.start.min(end)..end
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::io::Cursor;
-
-    #[test]
-    fn test_vecu8() {
-        let vec = vecu8(0);
-        assert_eq!(vec.len(), 0);
-        assert_eq!(vec.capacity(), 0);
-
-        let vec = vecu8(42);
-        assert_eq!(vec.len(), 42);
-        assert_eq!(vec.capacity(), 42);
-    }
-
-    #[test]
-    fn test_match_in_place() {
-        let bytes: Vec<u8> = vec![0, 1, 2];
-        let mut cursor = Cursor::new(bytes);
-        let mut seeker = ByteSeeker::new(&mut cursor);
-        assert_eq!(seeker.match_in_place(0, &[0]).unwrap(), true);
-        assert_eq!(seeker.match_in_place(0, &[0, 1]).unwrap(), true);
-        assert_eq!(seeker.match_in_place(0, &[0, 1, 2]).unwrap(), true);
-        assert_eq!(seeker.match_in_place(0, &[0, 1, 2, 3]).unwrap(), false);
-        assert_eq!(seeker.match_in_place(1, &[1]).unwrap(), true);
-        assert_eq!(seeker.match_in_place(1, &[1, 2]).unwrap(), true);
-        assert_eq!(seeker.match_in_place(1, &[1, 2, 3]).unwrap(), false);
+// Reads the bytes at `pos` of `stream` into `buf`, seeking only if `stream_pos` says the stream
+// is not positioned at `pos` already, and keeps `stream_pos` up to date.
+fn read_exact_at<R: Read + Seek>(
+    stream: &mut R,
+    stream_pos: &mut Option<u64>,
+    pos: u64,
+    buf: &mut [u8],
+) -> Result<()> {
+    if *stream_pos != Some(pos) {
+        stream.seek(SeekFrom::Start(pos))?;
     }
+    // Leave the position unknown if the read fails halfway.
+    *stream_pos = None;
+    stream.read_exact(buf)?;
+    *stream_pos = Some(pos + buf.len() as u64);
+    Ok(())
 }
@@ -0,0 +1,237 @@
+use crate::finder::{Match, Search};
+use std::convert::TryInto;
+use std::ops::Range;
+
+/// A window of a stream of bytes: `buf[..len]` holds the bytes at `pos..`.
+///
+/// The window does no I/O by itself. Searches report where the window must be moved to before
+/// they can go on, and moving the window reports which bytes must be read into the buffer, so
+/// both blocking and asynchronous seekers can drive it.
+#[derive(Debug)]
+pub(crate) struct Window {
+    pub(crate) buf: Vec<u8>,
+    pub(crate) pos: u64,
+    pub(crate) len: usize,
+}
+
+/// The outcome of searching the window.
+#[derive(Debug)]
+pub(crate) enum Step {
+    /// The search has finished, with or without a match.
+    Done(Option<Match>),
+    /// The window must be moved to the contained offset, and the search resumed from there.
+    Move(u64),
+}
+
+/// The bytes at `pos` of the underlying stream, which must be read into `range` of the buffer
+/// after the window was moved.
+#[derive(Clone, Debug)]
+pub(crate) struct Refill {
+    pub(crate) pos: u64,
+    pub(crate) range: Range<usize>,
+}
+
+impl Window {
+    pub(crate) fn new(size: usize) -> Self {
+        Self {
+            buf: vec![0; size],
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the offset right after the last byte of the window.
+    pub(crate) fn end(&self) -> u64 {
+        self.pos + self.len as u64
+    }
+
+    /// Returns the bytes at the given offsets, which must lie within the window.
+    pub(crate) fn bytes(&self, range: Range<u64>) -> &[u8] {
+        &self.buf[(range.start - self.pos) as usize..(range.end - self.pos) as usize]
+    }
+
+    /// Forgets the bytes of the window.
+    pub(crate) fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Resizes the buffer so that the window can hold a full chunk besides the carried over
+    /// bytes, but no larger than needed for the `limit` bytes that may be searched at most.
+    pub(crate) fn prepare(&mut self, chunk_size: usize, max_len: usize, limit: u64) {
+        let limit = limit.try_into().unwrap_or(usize::MAX);
+        // The window must still hold a whole match, should the stream grow.
+        let size = chunk_size
+            .saturating_add(max_len - 1)
+            .min(limit)
+            .max(max_len);
+        if self.buf.len() != size {
+            self.buf.resize(size, 0);
+            self.len = self.len.min(size);
+        }
+    }
+
+    /// Searches forwards for the first match within `start..end`.
+    pub(crate) fn find_forward<S: Search>(&self, searcher: &S, mut start: u64, end: u64) -> Step {
+        let (min_len, max_len) = (searcher.min_len() as u64, searcher.max_len() as u64);
+        loop {
+            if end.saturating_sub(start) < min_len {
+                return Step::Done(None);
+            }
+
+            if start < self.pos || self.end() < end.min(start + max_len) {
+                return Step::Move(start);
+            }
+
+            let hi = self.end().min(end);
+            if let Some(m) = searcher.find_in(self.bytes(start..hi)) {
+                let m = m.offset(start);
+                // A longer match starting earlier may still end beyond the window.
+                if hi == end || m.start + max_len <= hi {
+                    return Step::Done(Some(m));
+                }
+            } else if hi == end {
+                return Step::Done(None);
+            }
+            start = start.max(hi + 1 - max_len);
+        }
+    }
+
+    /// Searches backwards for the last match within `start..end`.
+    pub(crate) fn find_backward<S: Search>(&self, searcher: &S, start: u64, mut end: u64) -> Step {
+        let (min_len, max_len) = (searcher.min_len() as u64, searcher.max_len() as u64);
+        loop {
+            if end.saturating_sub(start) < min_len {
+                return Step::Done(None);
+            }
+
+            if end > self.end() || self.pos > start.max(end.saturating_sub(max_len)) {
+                return Step::Move(end);
+            }
+
+            let lo = self.pos.max(start);
+            if let Some(m) = searcher.rfind_in(self.bytes(lo..end)) {
+                let m = m.offset(lo);
+                // A longer match ending later may still start before the window.
+                if lo == start || lo + max_len <= m.end {
+                    return Step::Done(Some(m));
+                }
+            } else if lo == start {
+                return Step::Done(None);
+            }
+            end = end.min(lo + max_len - 1);
+        }
+    }
+
+    /// Moves the window so that it starts at `start`, without reading past `limit`.
+    ///
+    /// The bytes of the current window from `start` onwards are carried over to the front of the
+    /// buffer, and the rest of the buffer must be filled with the bytes that follow them.
+    pub(crate) fn move_forward(&mut self, start: u64, limit: u64) -> Refill {
+        let end = self.end();
+        let keep = if self.pos <= start && start <= end {
+            (end - start) as usize
+        } else {
+            0
+        };
+        self.buf.copy_within(self.len - keep..self.len, 0);
+
+        let pos = start + keep as u64;
+        let available = limit.saturating_sub(pos);
+        let read_len = (self.buf.len() - keep).min(available.try_into().unwrap_or(usize::MAX));
+        self.pos = start;
+        self.len = keep + read_len;
+        Refill {
+            pos,
+            range: keep..keep + read_len,
+        }
+    }
+
+    /// Moves the window so that it ends at `end`, without reading before `limit`.
+    ///
+    /// The bytes of the current window before `end` are carried over to the back of the buffer,
+    /// and the rest of the buffer must be filled with the bytes that precede them.
+    pub(crate) fn move_backward(&mut self, end: u64, limit: u64) -> Refill {
+        let keep = if self.pos <= end && end <= self.end() {
+            (end - self.pos) as usize
+        } else {
+            0
+        };
+
+        let available = (end - keep as u64).saturating_sub(limit);
+        let read_len = (self.buf.len() - keep).min(available.try_into().unwrap_or(usize::MAX));
+        self.buf.copy_within(..keep, read_len);
+        self.pos = end - keep as u64 - read_len as u64;
+        self.len = read_len + keep;
+        Refill {
+            pos: self.pos,
+            range: 0..read_len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finder::Finder;
+
+    // Drives the window over `bytes` the way a seeker does.
+    fn find(bytes: &[u8], needle: &[u8], chunk_size: usize, forward: bool) -> Vec<u64> {
+        let finder = Finder::new(needle);
+        let mut window = Window::new(1);
+        window.prepare(chunk_size, needle.len(), bytes.len() as u64);
+
+        let (mut start, mut end) = (0, bytes.len() as u64);
+        let mut found = Vec::new();
+        loop {
+            let step = if forward {
+                window.find_forward(&finder, start, end)
+            } else {
+                window.find_backward(&finder, start, end)
+            };
+            match step {
+                Step::Done(Some(m)) => {
+                    found.push(m.start);
+                    if forward {
+                        start = m.end;
+                    } else {
+                        end = m.start;
+                    }
+                }
+                Step::Done(None) => return found,
+                Step::Move(pos) => {
+                    // Offsets the search has passed need not be searched again.
+                    let refill = if forward {
+                        start = pos;
+                        window.move_forward(pos, bytes.len() as u64)
+                    } else {
+                        end = pos;
+                        window.move_backward(pos, 0)
+                    };
+                    let src = refill.pos as usize..refill.pos as usize + refill.range.len();
+                    window.buf[refill.range].copy_from_slice(&bytes[src]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        let bytes = b"ab\nc\r\nd\r\n\r\ne";
+        assert_eq!(find(bytes, b"\r\n", 1, true), vec![4, 7, 9]);
+        assert_eq!(find(bytes, b"\r\n", 1, false), vec![9, 7, 4]);
+        assert_eq!(find(bytes, b"\r\n", 64, true), vec![4, 7, 9]);
+        assert_eq!(find(bytes, b"x", 3, true), Vec::<u64>::new());
+        assert_eq!(find(bytes, b"\r\n", usize::MAX, false), vec![9, 7, 4]);
+    }
+
+    #[test]
+    fn test_prepare() {
+        let mut window = Window::new(1);
+        window.prepare(8, 3, 1024);
+        assert_eq!(window.buf.len(), 10);
+        window.prepare(usize::MAX, 3, 1024);
+        assert_eq!(window.buf.len(), 1024);
+        window.prepare(8, 3, 0);
+        assert_eq!(window.buf.len(), 3);
+    }
+}
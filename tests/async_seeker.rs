@@ -0,0 +1,219 @@
+#![cfg(feature = "tokio")]
+#![allow(clippy::assertions_on_constants)]
+
+use byteseeker::*;
+use std::future::Future;
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+// A byte stream that is pending every other time it is polled, and reads at most 3 bytes at once.
+struct Trickle {
+    inner: Cursor<Vec<u8>>,
+    ready: bool,
+}
+
+impl Trickle {
+    fn new(bytes: Vec<u8>) -> Self {
+        Self {
+            inner: Cursor::new(bytes),
+            ready: false,
+        }
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.ready = !self.ready;
+        if self.ready {
+            Poll::Ready(())
+        } else {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+impl AsyncRead for Trickle {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if this.poll_ready(cx).is_pending() {
+            return Poll::Pending;
+        }
+        let mut bytes = [0; 3];
+        let len = buf.remaining().min(bytes.len());
+        let n = this.inner.read(&mut bytes[..len])?;
+        buf.put_slice(&bytes[..n]);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncSeek for Trickle {
+    fn start_seek(self: Pin<&mut Self>, pos: SeekFrom) -> io::Result<()> {
+        self.get_mut().inner.seek(pos).map(|_| ())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        let this = self.get_mut();
+        if this.poll_ready(cx).is_pending() {
+            return Poll::Pending;
+        }
+        Poll::Ready(Ok(this.inner.position()))
+    }
+}
+
+// A waker that does nothing, for polling futures by hand.
+struct Noop;
+
+impl Wake for Noop {
+    fn wake(self: Arc<Self>) {}
+}
+
+fn records() -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for i in 0..300 {
+        bytes.extend_from_slice(format!("record {}\r\n", i * 7).as_bytes());
+    }
+    bytes
+}
+
+#[test]
+fn test_seek() {
+    let bytes = records();
+    let mut cursor = Cursor::new(bytes.clone());
+    let expected: Vec<u64> = ByteSeeker::new(&mut cursor)
+        .find_iter(b"\r\n")
+        .map(|pos| pos.unwrap())
+        .collect();
+
+    block_on(async {
+        let mut seeker = AsyncByteSeeker::with_capacity(Trickle::new(bytes), 4)
+            .await
+            .unwrap();
+        seeker.set_chunk_size(5);
+        for &pos in &expected {
+            assert_eq!(seeker.seek(b"\r\n").await.unwrap(), pos);
+        }
+        match seeker.seek(b"\r\n").await {
+            Ok(_) => assert!(false),
+            Err(e) => match *e.kind() {
+                ErrorKind::ByteNotFound => assert!(true),
+                _ => assert!(false),
+            },
+        }
+
+        seeker.reset();
+        for &pos in expected.iter().rev() {
+            assert_eq!(seeker.seek_back(b"\r\n").await.unwrap(), pos);
+        }
+
+        match seeker.seek(b"record").await {
+            Ok(_) => assert!(false),
+            Err(e) => match *e.kind() {
+                ErrorKind::UnsupportedLength => assert!(true),
+                _ => assert!(false),
+            },
+        }
+    });
+}
+
+#[test]
+fn test_seek_nth() {
+    block_on(async {
+        let mut seeker = AsyncByteSeeker::new(Trickle::new(records())).await.unwrap();
+        assert_eq!(seeker.seek_nth(b"record", 3).await.unwrap(), 20);
+        assert_eq!(seeker.seek_nth_back(b"record", 296).await.unwrap(), 42);
+        assert_eq!(seeker.seek_nth(b"record", 1).await.unwrap(), 31);
+        assert!(seeker.seek_nth(b"record", 1).await.is_err());
+
+        // A huge capacity is not allocated up front.
+        let mut seeker = AsyncByteSeeker::with_capacity(Trickle::new(records()), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(seeker.seek_nth(b"record", 3).await.unwrap(), 20);
+    });
+}
+
+#[test]
+fn test_find_stream() {
+    let bytes = records();
+    let mut cursor = Cursor::new(bytes.clone());
+    let expected: Vec<u64> = ByteSeeker::new(&mut cursor)
+        .find_iter(b"\n")
+        .map(|pos| pos.unwrap())
+        .collect();
+
+    block_on(async {
+        let mut seeker = AsyncByteSeeker::with_capacity(Trickle::new(bytes), 1)
+            .await
+            .unwrap();
+        seeker.set_chunk_size(1);
+
+        let mut found = Vec::new();
+        let mut stream = seeker.find_stream(b"\n");
+        while let Some(pos) = stream.next().await {
+            found.push(pos.unwrap());
+        }
+        assert_eq!(found, expected);
+        assert!(stream.next().await.is_none());
+
+        seeker.reset();
+        let mut found = Vec::new();
+        let mut stream = seeker.rfind_stream(b"\n");
+        while let Some(pos) = stream.next().await {
+            found.push(pos.unwrap());
+        }
+        found.reverse();
+        assert_eq!(found, expected);
+    });
+}
+
+#[test]
+fn test_dropped_search() {
+    let bytes = records();
+    block_on(async {
+        let mut seeker = AsyncByteSeeker::with_capacity(Trickle::new(bytes.clone()), 16)
+            .await
+            .unwrap();
+
+        // Abandon searches halfway through reading a chunk.
+        let waker = Waker::from(Arc::new(Noop));
+        let mut cx = Context::from_waker(&waker);
+        for _ in 0..3 {
+            let mut search = Box::pin(seeker.seek_back(b"record 7\r"));
+            assert!(search.as_mut().poll(&mut cx).is_pending());
+        }
+        assert_eq!(seeker.back_position(), bytes.len() as u64);
+
+        assert_eq!(seeker.seek_back(b"record 7\r").await.unwrap(), 10);
+        seeker.reset();
+        assert_eq!(seeker.seek(b"record 7\r").await.unwrap(), 10);
+
+        // Abandon a search halfway through seeking, and then move the underlying reader.
+        for polls in 1..40 {
+            let mut seeker = AsyncByteSeeker::with_capacity(Trickle::new(bytes.clone()), 16)
+                .await
+                .unwrap();
+            seeker.set_chunk_size(5);
+            {
+                let mut search = Box::pin(seeker.seek_back(b"record 7\r"));
+                for _ in 0..polls {
+                    assert!(search.as_mut().poll(&mut cx).is_pending());
+                }
+            }
+            seeker.get_mut().inner.set_position(5);
+            assert_eq!(seeker.seek_back(b"record 7\r").await.unwrap(), 10);
+        }
+    });
+}
@@ -1,6 +1,9 @@
+#![allow(clippy::assertions_on_constants)]
+
 use byteseeker::*;
 use std::io::Cursor;
 use std::iter;
+use std::ops::Range;
 
 const DEFAULT_CHUNK_SIZE: usize = 1024;
 
@@ -444,10 +447,16 @@ fn test_seek_csn() {
         .collect();
     let mut cursor = Cursor::new(bytes);
     let mut seeker = ByteSeeker::new(&mut cursor);
-    assert_eq!(seeker.seek(b"\n").unwrap(), DEFAULT_CHUNK_SIZE - 1);
-    assert_eq!(seeker.seek(b"\n").unwrap(), DEFAULT_CHUNK_SIZE);
-    assert_eq!(seeker.seek(b"\n").unwrap(), DEFAULT_CHUNK_SIZE * 2 + 1);
-    assert_eq!(seeker.seek(b"\n").unwrap(), DEFAULT_CHUNK_SIZE * 2 + 2);
+    assert_eq!(seeker.seek(b"\n").unwrap(), DEFAULT_CHUNK_SIZE as u64 - 1);
+    assert_eq!(seeker.seek(b"\n").unwrap(), DEFAULT_CHUNK_SIZE as u64);
+    assert_eq!(
+        seeker.seek(b"\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 2 + 1
+    );
+    assert_eq!(
+        seeker.seek(b"\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 2 + 2
+    );
     match seeker.seek(b"\n") {
         Ok(_) => assert!(false),
         Err(e) => match *e.kind() {
@@ -464,8 +473,11 @@ fn test_seek_csn() {
         .collect();
     let mut cursor = Cursor::new(bytes);
     let mut seeker = ByteSeeker::new(&mut cursor);
-    assert_eq!(seeker.seek(b"\n\n").unwrap(), DEFAULT_CHUNK_SIZE - 1);
-    assert_eq!(seeker.seek(b"\n\n").unwrap(), DEFAULT_CHUNK_SIZE * 2 + 1);
+    assert_eq!(seeker.seek(b"\n\n").unwrap(), DEFAULT_CHUNK_SIZE as u64 - 1);
+    assert_eq!(
+        seeker.seek(b"\n\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 2 + 1
+    );
     match seeker.seek(b"\n\n") {
         Ok(_) => assert!(false),
         Err(e) => match *e.kind() {
@@ -485,10 +497,19 @@ fn test_seek_back_csn() {
         .collect();
     let mut cursor = Cursor::new(bytes);
     let mut seeker = ByteSeeker::new(&mut cursor);
-    assert_eq!(seeker.seek_back(b"\n").unwrap(), DEFAULT_CHUNK_SIZE * 2 + 2);
-    assert_eq!(seeker.seek_back(b"\n").unwrap(), DEFAULT_CHUNK_SIZE * 2 + 1);
-    assert_eq!(seeker.seek_back(b"\n").unwrap(), DEFAULT_CHUNK_SIZE);
-    assert_eq!(seeker.seek_back(b"\n").unwrap(), DEFAULT_CHUNK_SIZE - 1);
+    assert_eq!(
+        seeker.seek_back(b"\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 2 + 2
+    );
+    assert_eq!(
+        seeker.seek_back(b"\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 2 + 1
+    );
+    assert_eq!(seeker.seek_back(b"\n").unwrap(), DEFAULT_CHUNK_SIZE as u64);
+    assert_eq!(
+        seeker.seek_back(b"\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 - 1
+    );
     match seeker.seek_back(b"\n") {
         Ok(_) => assert!(false),
         Err(e) => match *e.kind() {
@@ -507,9 +528,12 @@ fn test_seek_back_csn() {
     let mut seeker = ByteSeeker::new(&mut cursor);
     assert_eq!(
         seeker.seek_back(b"\n\n").unwrap(),
-        DEFAULT_CHUNK_SIZE * 2 + 1
+        DEFAULT_CHUNK_SIZE as u64 * 2 + 1
+    );
+    assert_eq!(
+        seeker.seek_back(b"\n\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 - 1
     );
-    assert_eq!(seeker.seek_back(b"\n\n").unwrap(), DEFAULT_CHUNK_SIZE - 1);
     match seeker.seek_back(b"\n\n") {
         Ok(_) => assert!(false),
         Err(e) => match *e.kind() {
@@ -532,20 +556,29 @@ fn test_seek_nth() {
 
     let mut cursor = Cursor::new(bytes.clone());
     let mut seeker = ByteSeeker::new(&mut cursor);
-    assert_eq!(seeker.seek_nth(b"\n", 1).unwrap(), DEFAULT_CHUNK_SIZE - 1);
-    assert_eq!(seeker.seek_nth(b"\n", 1).unwrap(), DEFAULT_CHUNK_SIZE);
-    assert_eq!(seeker.seek_nth(b"\n", 1).unwrap(), 2 * DEFAULT_CHUNK_SIZE);
     assert_eq!(
         seeker.seek_nth(b"\n", 1).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE + 1
+        DEFAULT_CHUNK_SIZE as u64 - 1
     );
     assert_eq!(
         seeker.seek_nth(b"\n", 1).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE + 100 + 2
+        DEFAULT_CHUNK_SIZE as u64
     );
     assert_eq!(
         seeker.seek_nth(b"\n", 1).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE + 100 + 3
+        2 * DEFAULT_CHUNK_SIZE as u64
+    );
+    assert_eq!(
+        seeker.seek_nth(b"\n", 1).unwrap(),
+        2 * DEFAULT_CHUNK_SIZE as u64 + 1
+    );
+    assert_eq!(
+        seeker.seek_nth(b"\n", 1).unwrap(),
+        2 * DEFAULT_CHUNK_SIZE as u64 + 100 + 2
+    );
+    assert_eq!(
+        seeker.seek_nth(b"\n", 1).unwrap(),
+        2 * DEFAULT_CHUNK_SIZE as u64 + 100 + 3
     );
     match seeker.seek_nth(b"\n", 1) {
         Ok(_) => assert!(false),
@@ -557,14 +590,17 @@ fn test_seek_nth() {
 
     let mut cursor = Cursor::new(bytes.clone());
     let mut seeker = ByteSeeker::new(&mut cursor);
-    assert_eq!(seeker.seek_nth(b"\n", 2).unwrap(), DEFAULT_CHUNK_SIZE);
     assert_eq!(
         seeker.seek_nth(b"\n", 2).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE + 1
+        DEFAULT_CHUNK_SIZE as u64
+    );
+    assert_eq!(
+        seeker.seek_nth(b"\n", 2).unwrap(),
+        2 * DEFAULT_CHUNK_SIZE as u64 + 1
     );
     assert_eq!(
         seeker.seek_nth(b"\n", 2).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE + 100 + 3
+        2 * DEFAULT_CHUNK_SIZE as u64 + 100 + 3
     );
     match seeker.seek_nth(b"\n", 1) {
         Ok(_) => assert!(false),
@@ -576,11 +612,17 @@ fn test_seek_nth() {
 
     let mut cursor = Cursor::new(bytes.clone());
     let mut seeker = ByteSeeker::new(&mut cursor);
-    assert_eq!(seeker.seek_nth(b"\n", 1).unwrap(), DEFAULT_CHUNK_SIZE - 1);
-    assert_eq!(seeker.seek_nth(b"\n", 2).unwrap(), 2 * DEFAULT_CHUNK_SIZE);
+    assert_eq!(
+        seeker.seek_nth(b"\n", 1).unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 - 1
+    );
+    assert_eq!(
+        seeker.seek_nth(b"\n", 2).unwrap(),
+        2 * DEFAULT_CHUNK_SIZE as u64
+    );
     assert_eq!(
         seeker.seek_nth(b"\n", 3).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE + 100 + 3
+        2 * DEFAULT_CHUNK_SIZE as u64 + 100 + 3
     );
     match seeker.seek_nth(b"\n", 1) {
         Ok(_) => assert!(false),
@@ -592,11 +634,17 @@ fn test_seek_nth() {
 
     let mut cursor = Cursor::new(bytes.clone());
     let mut seeker = ByteSeeker::new(&mut cursor);
-    assert_eq!(seeker.seek_nth(b"\n\n", 1).unwrap(), DEFAULT_CHUNK_SIZE - 1);
-    assert_eq!(seeker.seek_nth(b"\n\n", 1).unwrap(), 2 * DEFAULT_CHUNK_SIZE);
     assert_eq!(
         seeker.seek_nth(b"\n\n", 1).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE + 100 + 2
+        DEFAULT_CHUNK_SIZE as u64 - 1
+    );
+    assert_eq!(
+        seeker.seek_nth(b"\n\n", 1).unwrap(),
+        2 * DEFAULT_CHUNK_SIZE as u64
+    );
+    assert_eq!(
+        seeker.seek_nth(b"\n\n", 1).unwrap(),
+        2 * DEFAULT_CHUNK_SIZE as u64 + 100 + 2
     );
     match seeker.seek_nth(b"\n\n", 1) {
         Ok(_) => assert!(false),
@@ -608,7 +656,10 @@ fn test_seek_nth() {
 
     let mut cursor = Cursor::new(bytes.clone());
     let mut seeker = ByteSeeker::new(&mut cursor);
-    assert_eq!(seeker.seek_nth(b"\n\n", 2).unwrap(), 2 * DEFAULT_CHUNK_SIZE);
+    assert_eq!(
+        seeker.seek_nth(b"\n\n", 2).unwrap(),
+        2 * DEFAULT_CHUNK_SIZE as u64
+    );
     match seeker.seek_nth(b"\n\n", 2) {
         Ok(_) => assert!(false),
         Err(e) => match *e.kind() {
@@ -619,10 +670,13 @@ fn test_seek_nth() {
 
     let mut cursor = Cursor::new(bytes.clone());
     let mut seeker = ByteSeeker::new(&mut cursor);
-    assert_eq!(seeker.seek_nth(b"\n\n", 1).unwrap(), DEFAULT_CHUNK_SIZE - 1);
+    assert_eq!(
+        seeker.seek_nth(b"\n\n", 1).unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 - 1
+    );
     assert_eq!(
         seeker.seek_nth(b"\n\n", 2).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE + 100 + 2
+        2 * DEFAULT_CHUNK_SIZE as u64 + 100 + 2
     );
     match seeker.seek_nth(b"\n\n", 1) {
         Ok(_) => assert!(false),
@@ -648,24 +702,27 @@ fn test_seek_nth_back() {
     let mut seeker = ByteSeeker::new(&mut cursor);
     assert_eq!(
         seeker.seek_nth_back(b"\n", 1).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE + 100 + 3
+        2 * DEFAULT_CHUNK_SIZE as u64 + 100 + 3
+    );
+    assert_eq!(
+        seeker.seek_nth_back(b"\n", 1).unwrap(),
+        2 * DEFAULT_CHUNK_SIZE as u64 + 100 + 2
     );
     assert_eq!(
         seeker.seek_nth_back(b"\n", 1).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE + 100 + 2
+        2 * DEFAULT_CHUNK_SIZE as u64 + 1
     );
     assert_eq!(
         seeker.seek_nth_back(b"\n", 1).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE + 1
+        2 * DEFAULT_CHUNK_SIZE as u64
     );
     assert_eq!(
         seeker.seek_nth_back(b"\n", 1).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE
+        DEFAULT_CHUNK_SIZE as u64
     );
-    assert_eq!(seeker.seek_nth_back(b"\n", 1).unwrap(), DEFAULT_CHUNK_SIZE);
     assert_eq!(
         seeker.seek_nth_back(b"\n", 1).unwrap(),
-        DEFAULT_CHUNK_SIZE - 1
+        DEFAULT_CHUNK_SIZE as u64 - 1
     );
     match seeker.seek_nth_back(b"\n", 1) {
         Ok(_) => assert!(false),
@@ -679,15 +736,15 @@ fn test_seek_nth_back() {
     let mut seeker = ByteSeeker::new(&mut cursor);
     assert_eq!(
         seeker.seek_nth_back(b"\n", 2).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE + 100 + 2
+        2 * DEFAULT_CHUNK_SIZE as u64 + 100 + 2
     );
     assert_eq!(
         seeker.seek_nth_back(b"\n", 2).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE
+        2 * DEFAULT_CHUNK_SIZE as u64
     );
     assert_eq!(
         seeker.seek_nth_back(b"\n", 2).unwrap(),
-        DEFAULT_CHUNK_SIZE - 1
+        DEFAULT_CHUNK_SIZE as u64 - 1
     );
     match seeker.seek_nth_back(b"\n", 2) {
         Ok(_) => assert!(false),
@@ -701,15 +758,15 @@ fn test_seek_nth_back() {
     let mut seeker = ByteSeeker::new(&mut cursor);
     assert_eq!(
         seeker.seek_nth_back(b"\n", 1).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE + 100 + 3
+        2 * DEFAULT_CHUNK_SIZE as u64 + 100 + 3
     );
     assert_eq!(
         seeker.seek_nth_back(b"\n", 2).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE + 1
+        2 * DEFAULT_CHUNK_SIZE as u64 + 1
     );
     assert_eq!(
         seeker.seek_nth_back(b"\n", 3).unwrap(),
-        DEFAULT_CHUNK_SIZE - 1
+        DEFAULT_CHUNK_SIZE as u64 - 1
     );
     match seeker.seek_nth_back(b"\n", 1) {
         Ok(_) => assert!(false),
@@ -723,15 +780,15 @@ fn test_seek_nth_back() {
     let mut seeker = ByteSeeker::new(&mut cursor);
     assert_eq!(
         seeker.seek_nth_back(b"\n\n", 1).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE + 100 + 2
+        2 * DEFAULT_CHUNK_SIZE as u64 + 100 + 2
     );
     assert_eq!(
         seeker.seek_nth_back(b"\n\n", 1).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE
+        2 * DEFAULT_CHUNK_SIZE as u64
     );
     assert_eq!(
         seeker.seek_nth_back(b"\n\n", 1).unwrap(),
-        DEFAULT_CHUNK_SIZE - 1
+        DEFAULT_CHUNK_SIZE as u64 - 1
     );
     match seeker.seek_nth_back(b"\n\n", 1) {
         Ok(_) => assert!(false),
@@ -745,7 +802,7 @@ fn test_seek_nth_back() {
     let mut seeker = ByteSeeker::new(&mut cursor);
     assert_eq!(
         seeker.seek_nth_back(b"\n\n", 2).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE
+        2 * DEFAULT_CHUNK_SIZE as u64
     );
     match seeker.seek_nth_back(b"\n\n", 2) {
         Ok(_) => assert!(false),
@@ -759,11 +816,11 @@ fn test_seek_nth_back() {
     let mut seeker = ByteSeeker::new(&mut cursor);
     assert_eq!(
         seeker.seek_nth_back(b"\n\n", 1).unwrap(),
-        2 * DEFAULT_CHUNK_SIZE + 100 + 2
+        2 * DEFAULT_CHUNK_SIZE as u64 + 100 + 2
     );
     assert_eq!(
         seeker.seek_nth_back(b"\n\n", 2).unwrap(),
-        DEFAULT_CHUNK_SIZE - 1
+        DEFAULT_CHUNK_SIZE as u64 - 1
     );
     match seeker.seek_nth_back(b"\n\n", 1) {
         Ok(_) => assert!(false),
@@ -773,3 +830,1282 @@ fn test_seek_nth_back() {
         },
     }
 }
+
+#[test]
+fn test_seek_frequent_first_byte() {
+    let bytes: Vec<u8> = iter::repeat(b"\n2023-".iter().cloned())
+        .take(DEFAULT_CHUNK_SIZE / 6)
+        .flatten()
+        .chain(iter::repeat(b'\n').take(DEFAULT_CHUNK_SIZE))
+        .chain(b"\n2024-".iter().cloned())
+        .chain(iter::repeat(b'\n').take(3))
+        .chain(b"\n2024-".iter().cloned())
+        .collect();
+    let first = (DEFAULT_CHUNK_SIZE / 6 * 6 + DEFAULT_CHUNK_SIZE) as u64;
+    let second = first + 9;
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    assert_eq!(seeker.seek(b"\n2024-").unwrap(), first);
+    assert_eq!(seeker.seek(b"\n2024-").unwrap(), second);
+    match seeker.seek(b"\n2024-") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    assert_eq!(seeker.seek_back(b"\n2024-").unwrap(), second);
+    assert_eq!(seeker.seek_back(b"\n2024-").unwrap(), first);
+    match seeker.seek_back(b"\n2024-") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+}
+
+struct CountingCursor {
+    inner: Cursor<Vec<u8>>,
+    seeks: usize,
+    read: usize,
+}
+
+impl std::io::Read for CountingCursor {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read += n;
+        Ok(n)
+    }
+}
+
+impl std::io::Seek for CountingCursor {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.seeks += 1;
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn test_seek_across_chunks_without_seeking_stream() {
+    let bytes: Vec<u8> = iter::repeat(0)
+        .take(DEFAULT_CHUNK_SIZE - 1)
+        .chain(b"\r\n".iter().cloned())
+        .chain(iter::repeat(0).take(DEFAULT_CHUNK_SIZE - 2))
+        .chain(b"\r\n".iter().cloned())
+        .chain(iter::repeat(0).take(DEFAULT_CHUNK_SIZE))
+        .collect();
+    let mut cursor = CountingCursor {
+        inner: Cursor::new(bytes),
+        seeks: 0,
+        read: 0,
+    };
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    assert_eq!(seeker.seek(b"\r\n").unwrap(), DEFAULT_CHUNK_SIZE as u64 - 1);
+    assert_eq!(
+        seeker.seek(b"\r\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 2 - 1
+    );
+    match seeker.seek(b"\r\n") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+    // Only the two seeks done while measuring the length of the stream.
+    assert_eq!(cursor.seeks, 2);
+}
+
+#[test]
+fn test_seek_back_across_chunks() {
+    let bytes: Vec<u8> = iter::repeat(0)
+        .take(DEFAULT_CHUNK_SIZE)
+        .chain(b"\r\n".iter().cloned())
+        .chain(iter::repeat(0).take(DEFAULT_CHUNK_SIZE - 2))
+        .chain(b"\r\n".iter().cloned())
+        .chain(iter::repeat(0).take(DEFAULT_CHUNK_SIZE - 1))
+        .collect();
+    let mut cursor = CountingCursor {
+        inner: Cursor::new(bytes),
+        seeks: 0,
+        read: 0,
+    };
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    assert_eq!(
+        seeker.seek_back(b"\r\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 2
+    );
+    assert_eq!(
+        seeker.seek_back(b"\r\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64
+    );
+    match seeker.seek_back(b"\r\n") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+    // One seek per chunk read, besides the two seeks done while measuring the length.
+    assert_eq!(cursor.seeks, 2 + 3);
+}
+
+#[test]
+fn test_find_iter() {
+    let bytes: Vec<u8> = iter::repeat(0)
+        .take(DEFAULT_CHUNK_SIZE - 1)
+        .chain(iter::repeat(b'\n').take(2))
+        .chain(iter::repeat(0).take(DEFAULT_CHUNK_SIZE))
+        .chain(iter::repeat(b'\n').take(2))
+        .collect();
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    let offsets: Vec<u64> = seeker.find_iter(b"\n").map(|x| x.unwrap()).collect();
+    assert_eq!(
+        offsets,
+        vec![
+            DEFAULT_CHUNK_SIZE as u64 - 1,
+            DEFAULT_CHUNK_SIZE as u64,
+            DEFAULT_CHUNK_SIZE as u64 * 2 + 1,
+            DEFAULT_CHUNK_SIZE as u64 * 2 + 2
+        ]
+    );
+    assert!(seeker.find_iter(b"\n").next().is_none());
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    let offsets: Vec<u64> = seeker.rfind_iter(b"\n\n").map(|x| x.unwrap()).collect();
+    assert_eq!(
+        offsets,
+        vec![
+            DEFAULT_CHUNK_SIZE as u64 * 2 + 1,
+            DEFAULT_CHUNK_SIZE as u64 - 1
+        ]
+    );
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    let mut iter = seeker.find_iter(b"\n");
+    assert_eq!(iter.next().unwrap().unwrap(), DEFAULT_CHUNK_SIZE as u64 - 1);
+    assert_eq!(
+        iter.next_back().unwrap().unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 2 + 2
+    );
+    assert_eq!(
+        iter.next_back().unwrap().unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 2 + 1
+    );
+    assert_eq!(iter.next().unwrap().unwrap(), DEFAULT_CHUNK_SIZE as u64);
+    assert!(iter.next().is_none());
+    assert!(iter.next_back().is_none());
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    let mut iter = seeker.find_iter(&[]);
+    match iter.next() {
+        Some(Err(e)) => match *e.kind() {
+            ErrorKind::UnsupportedLength => assert!(true),
+            _ => assert!(false),
+        },
+        _ => assert!(false),
+    }
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_seek_any() {
+    let bytes: Vec<u8> = iter::repeat(0)
+        .take(DEFAULT_CHUNK_SIZE)
+        .chain(b"abcd".iter().cloned())
+        .chain(iter::repeat(0).take(DEFAULT_CHUNK_SIZE))
+        .chain(b"bc".iter().cloned())
+        .collect();
+    let patterns: &[&[u8]] = &[b"bc", b"abcd"];
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    assert_eq!(
+        seeker.seek_any(patterns).unwrap(),
+        Match {
+            start: DEFAULT_CHUNK_SIZE as u64,
+            end: DEFAULT_CHUNK_SIZE as u64 + 4,
+            pattern_id: 1,
+        }
+    );
+    let m = seeker.seek_any(patterns).unwrap();
+    assert_eq!(m.pattern_id, 0);
+    assert_eq!(
+        Range::from(m),
+        DEFAULT_CHUNK_SIZE as u64 * 2 + 4..DEFAULT_CHUNK_SIZE as u64 * 2 + 6
+    );
+    match seeker.seek_any(patterns) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    match seeker.seek_any(&[b"\n", b""]) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::UnsupportedLength => assert!(true),
+            _ => assert!(false),
+        },
+    }
+}
+
+#[test]
+fn test_seek_any_back() {
+    let bytes: Vec<u8> = b"bc"
+        .iter()
+        .cloned()
+        .chain(iter::repeat(0).take(DEFAULT_CHUNK_SIZE))
+        .chain(b"abcd".iter().cloned())
+        .chain(iter::repeat(0).take(DEFAULT_CHUNK_SIZE))
+        .collect();
+    let patterns: &[&[u8]] = &[b"bc", b"abcd"];
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    assert_eq!(
+        seeker.seek_any_back(patterns).unwrap(),
+        Match {
+            start: DEFAULT_CHUNK_SIZE as u64 + 2,
+            end: DEFAULT_CHUNK_SIZE as u64 + 6,
+            pattern_id: 1,
+        }
+    );
+    assert_eq!(
+        seeker.seek_any_back(patterns).unwrap(),
+        Match {
+            start: 0,
+            end: 2,
+            pattern_id: 0,
+        }
+    );
+    match seeker.seek_any_back(patterns) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+}
+
+#[test]
+fn test_seek_with_finder() {
+    let bytes: Vec<u8> = iter::repeat(0)
+        .take(DEFAULT_CHUNK_SIZE - 1)
+        .chain(iter::repeat(b'\n').take(2))
+        .chain(iter::repeat(0).take(DEFAULT_CHUNK_SIZE))
+        .chain(iter::repeat(b'\n').take(2))
+        .collect();
+    let finder = Finder::new(b"\n\n");
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    assert_eq!(
+        seeker.seek_with(&finder).unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 - 1
+    );
+    assert_eq!(
+        seeker.seek_with(&finder).unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 2 + 1
+    );
+    match seeker.seek_with(&finder) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    assert_eq!(
+        seeker.seek_back_with(&finder).unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 2 + 1
+    );
+    assert_eq!(
+        seeker.seek_back_with(&finder).unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 - 1
+    );
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::with_capacity(&mut cursor, 1);
+    match seeker.seek_with(&finder) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::UnsupportedLength => assert!(true),
+            _ => assert!(false),
+        },
+    }
+}
+
+struct Unseekable;
+
+impl std::io::Read for Unseekable {
+    fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+        Ok(0)
+    }
+}
+
+impl std::io::Seek for Unseekable {
+    fn seek(&mut self, _: std::io::SeekFrom) -> std::io::Result<u64> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "illegal seek",
+        ))
+    }
+}
+
+#[test]
+fn test_try_new() {
+    let mut stream = Unseekable;
+    match ByteSeeker::try_new(&mut stream) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::Io(_) => assert!(true),
+            _ => assert!(false),
+        },
+    }
+    match ByteSeeker::try_with_capacity(&mut stream, 3) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::Io(_) => assert!(true),
+            _ => assert!(false),
+        },
+    }
+
+    let bytes: Vec<u8> = vec![b'\n'];
+    let mut cursor = Cursor::new(bytes);
+    let mut seeker = ByteSeeker::try_new(&mut cursor).unwrap();
+    assert_eq!(seeker.seek(b"\n").unwrap(), 0);
+    seeker.try_reset().unwrap();
+    assert_eq!(seeker.seek(b"\n").unwrap(), 0);
+}
+
+#[test]
+fn test_chunk_size() {
+    let bytes: Vec<u8> = iter::repeat(0)
+        .take(DEFAULT_CHUNK_SIZE * 3)
+        .chain(b"\r\n".iter().cloned())
+        .collect();
+
+    // A small capacity does not shrink the chunks below the default size.
+    let mut cursor = CountingCursor {
+        inner: Cursor::new(bytes.clone()),
+        seeks: 0,
+        read: 0,
+    };
+    let mut seeker = ByteSeeker::with_capacity(&mut cursor, 2);
+    assert_eq!(seeker.chunk_size(), DEFAULT_CHUNK_SIZE);
+    assert_eq!(
+        seeker.seek_back(b"\r\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 3
+    );
+    assert_eq!(cursor.seeks, 2 + 1);
+
+    // Unless the chunk size is lowered explicitly.
+    let mut cursor = CountingCursor {
+        inner: Cursor::new(bytes.clone()),
+        seeks: 0,
+        read: 0,
+    };
+    let mut seeker = ByteSeeker::with_capacity(&mut cursor, 2);
+    seeker.set_chunk_size(2);
+    assert_eq!(
+        seeker.seek_back(b"\r\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 3
+    );
+    match seeker.seek_back(b"\r\n") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+    assert_eq!(cursor.seeks, 2 + DEFAULT_CHUNK_SIZE * 3 / 2 + 1);
+
+    // While a large chunk size reads the whole stream at once.
+    let mut cursor = CountingCursor {
+        inner: Cursor::new(bytes.clone()),
+        seeks: 0,
+        read: 0,
+    };
+    let mut seeker = ByteSeeker::with_capacity(&mut cursor, 2);
+    seeker.set_chunk_size(DEFAULT_CHUNK_SIZE * 4);
+    assert_eq!(
+        seeker.seek_back(b"\r\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 3
+    );
+    match seeker.seek_back(b"\r\n") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+    // The single chunk starts at offset 0, where the stream already is.
+    assert_eq!(cursor.seeks, 2);
+
+    // No more than the whole stream is buffered, however large the chunk size.
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::with_capacity(&mut cursor, 2);
+    seeker.set_chunk_size(usize::MAX);
+    assert_eq!(seeker.seek(b"\r").unwrap(), DEFAULT_CHUNK_SIZE as u64 * 3);
+    assert_eq!(
+        seeker.seek_back(b"\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 3 + 1
+    );
+
+    // A huge capacity is not allocated up front.
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::try_with_capacity(&mut cursor, usize::MAX).unwrap();
+    assert_eq!(seeker.capacity(), usize::MAX);
+    assert_eq!(seeker.seek(b"\r\n").unwrap(), DEFAULT_CHUNK_SIZE as u64 * 3);
+    seeker.reset();
+    assert_eq!(
+        seeker.seek_back(b"\r\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 3
+    );
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::with_capacity(&mut cursor, 2);
+    seeker.set_chunk_size(DEFAULT_CHUNK_SIZE * 4);
+    match seeker.seek(b"\r\n\r") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::UnsupportedLength => assert!(true),
+            _ => assert!(false),
+        },
+    }
+}
+
+#[test]
+fn test_seek_both_ends() {
+    let bytes: Vec<u8> = iter::repeat(0)
+        .take(DEFAULT_CHUNK_SIZE - 1)
+        .chain(iter::repeat(b'\n').take(2))
+        .chain(iter::repeat(0).take(DEFAULT_CHUNK_SIZE))
+        .chain(iter::repeat(b'\n').take(2))
+        .collect();
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    assert_eq!(seeker.seek(b"\n").unwrap(), DEFAULT_CHUNK_SIZE as u64 - 1);
+    assert_eq!(
+        seeker.seek_back(b"\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 2 + 2
+    );
+    assert_eq!(
+        seeker.seek_back(b"\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 2 + 1
+    );
+    // A failed search in one direction leaves the other one usable.
+    match seeker.seek(b"\n\n") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+    assert_eq!(seeker.seek_back(b"\n").unwrap(), DEFAULT_CHUNK_SIZE as u64);
+    match seeker.seek(b"\n") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+    match seeker.seek_back(b"\n") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+}
+
+#[test]
+fn test_set_position() {
+    let bytes: Vec<u8> = iter::repeat(0)
+        .take(DEFAULT_CHUNK_SIZE - 1)
+        .chain(iter::repeat(b'\n').take(2))
+        .chain(iter::repeat(0).take(DEFAULT_CHUNK_SIZE))
+        .chain(iter::repeat(b'\n').take(2))
+        .collect();
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    seeker.set_position(DEFAULT_CHUNK_SIZE as u64);
+    assert_eq!(seeker.seek(b"\n").unwrap(), DEFAULT_CHUNK_SIZE as u64);
+    assert_eq!(
+        seeker.seek_back(b"\n\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 2 + 1
+    );
+    assert_eq!(
+        seeker.seek_from(0, b"\n\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 - 1
+    );
+    assert_eq!(
+        seeker
+            .seek_back_from(DEFAULT_CHUNK_SIZE as u64 * 3, b"\n")
+            .unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 2 + 2
+    );
+    assert_eq!(seeker.back_position(), DEFAULT_CHUNK_SIZE as u64 * 2 + 2);
+
+    // Cursors set past each other find nothing.
+    seeker.set_position(DEFAULT_CHUNK_SIZE as u64 * 2);
+    seeker.set_back_position(DEFAULT_CHUNK_SIZE as u64);
+    match seeker.seek(b"\n") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+    match seeker.seek_back(b"\n") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+
+    seeker.set_position(u64::MAX);
+    assert_eq!(seeker.position(), seeker.len());
+}
+
+// A cursor that fails reads which touch bytes outside of the given range.
+struct FencedCursor {
+    inner: Cursor<Vec<u8>>,
+    fence: std::ops::Range<u64>,
+}
+
+impl std::io::Read for FencedCursor {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let pos = self.inner.position();
+        let len = buf.len().min((self.fence.end.saturating_sub(pos)) as usize);
+        if pos < self.fence.start || (len == 0 && !buf.is_empty()) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "read outside of the fence",
+            ));
+        }
+        self.inner.read(&mut buf[..len])
+    }
+}
+
+impl std::io::Seek for FencedCursor {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn test_with_range() {
+    let bytes: Vec<u8> = iter::repeat(b'\n')
+        .take(DEFAULT_CHUNK_SIZE)
+        .chain(b"0\r\n1\r\n2\r\n".iter().cloned())
+        .chain(iter::repeat(b'\n').take(DEFAULT_CHUNK_SIZE * 2))
+        .collect();
+    let len = bytes.len() as u64;
+    let range = DEFAULT_CHUNK_SIZE as u64 + 2..DEFAULT_CHUNK_SIZE as u64 + 8;
+    let mut cursor = FencedCursor {
+        inner: Cursor::new(bytes),
+        fence: range.clone(),
+    };
+    let mut seeker = ByteSeeker::with_range(&mut cursor, range.clone());
+    assert_eq!(seeker.len(), len);
+    assert_eq!(seeker.range(), range);
+    assert_eq!(seeker.position(), range.start);
+    assert_eq!(seeker.back_position(), range.end);
+
+    // The `\r\n`s straddling the ends of the range are not found.
+    assert_eq!(seeker.seek(b"\r\n").unwrap(), DEFAULT_CHUNK_SIZE as u64 + 4);
+    match seeker.seek(b"\r\n") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+    seeker.reset();
+    assert_eq!(
+        seeker.seek_back(b"\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 + 5
+    );
+    assert_eq!(
+        seeker.seek_back(b"\n").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 + 2
+    );
+    match seeker.seek_back(b"\n") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+
+    seeker.set_position(0);
+    assert_eq!(seeker.position(), range.start);
+    seeker.set_back_position(u64::MAX);
+    assert_eq!(seeker.back_position(), range.end);
+
+    seeker.set_range(len - 1..u64::MAX);
+    assert_eq!(seeker.range(), len - 1..len);
+    let (start, end) = (u64::MAX, 0);
+    seeker.set_range(start..end);
+    assert_eq!(seeker.range(), 0..0);
+}
+
+#[test]
+fn test_owned_stream() {
+    let bytes = b"0\n1\n2\n".to_vec();
+    let mut seeker = ByteSeeker::new(Cursor::new(bytes.clone()));
+    assert_eq!(seeker.seek(b"\n").unwrap(), 1);
+
+    // An owning `ByteSeeker` can be moved to another thread along with its stream.
+    let handle = std::thread::spawn(move || {
+        let pos = seeker.seek_back(b"\n").unwrap();
+        (pos, seeker)
+    });
+    let (pos, seeker) = handle.join().unwrap();
+    assert_eq!(pos, 5);
+    assert_eq!(seeker.get_ref().get_ref(), &bytes);
+    assert_eq!(seeker.into_inner().into_inner(), bytes);
+}
+
+#[test]
+fn test_read_helpers() {
+    let bytes: Vec<u8> = iter::repeat(b'0')
+        .take(DEFAULT_CHUNK_SIZE - 1)
+        .chain(b"<a>".iter().cloned())
+        .chain(iter::repeat(b'1').take(DEFAULT_CHUNK_SIZE))
+        .chain(b"\n2".iter().cloned())
+        .collect();
+    let len = bytes.len() as u64;
+    let mut cursor = CountingCursor {
+        inner: Cursor::new(bytes.clone()),
+        seeks: 0,
+        read: 0,
+    };
+    let mut seeker = ByteSeeker::new(&mut cursor);
+
+    let mut buf = Vec::new();
+    assert_eq!(
+        seeker.read_between(b"<", b">", &mut buf).unwrap(),
+        DEFAULT_CHUNK_SIZE as u64
+    );
+    assert_eq!(buf, b"a");
+
+    // A failed search appends nothing and leaves the cursor where it was.
+    match seeker.read_until_match(b"<", &mut buf) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+    assert_eq!(buf, b"a");
+    assert_eq!(seeker.position(), DEFAULT_CHUNK_SIZE as u64 + 2);
+
+    buf.clear();
+    assert_eq!(seeker.read_until_match(b"\n", &mut buf).unwrap(), len - 2);
+    assert_eq!(&buf[..], &bytes[DEFAULT_CHUNK_SIZE + 2..len as usize - 2]);
+
+    // The forward cursor is past the `\n` now.
+    seeker.set_position(0);
+    buf.clear();
+    assert_eq!(
+        seeker.read_from_match_to_end(b"\n", &mut buf).unwrap(),
+        len - 2
+    );
+    assert_eq!(buf, b"2");
+
+    // Bytes within the window of the last search are not read again.
+    let mut cursor = CountingCursor {
+        inner: Cursor::new(b"0\n1\n2".to_vec()),
+        seeks: 0,
+        read: 0,
+    };
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    buf.clear();
+    assert_eq!(seeker.read_until_match(b"\n", &mut buf).unwrap(), 1);
+    assert_eq!(seeker.read_from_match_to_end(b"\n", &mut buf).unwrap(), 3);
+    assert_eq!(buf, b"02");
+    assert_eq!(cursor.read, 5);
+}
+
+#[test]
+fn test_lines_back() {
+    fn lines_back(bytes: &[u8]) -> Vec<(u64, Vec<u8>)> {
+        let mut cursor = Cursor::new(bytes);
+        let mut seeker = ByteSeeker::new(&mut cursor);
+        seeker.lines_back().map(|x| x.unwrap()).collect()
+    }
+
+    assert_eq!(lines_back(b""), vec![]);
+    assert_eq!(lines_back(b"\n"), vec![(0, b"".to_vec())]);
+    assert_eq!(lines_back(b"\r\n"), vec![(0, b"".to_vec())]);
+    assert_eq!(lines_back(b"0"), vec![(0, b"0".to_vec())]);
+    assert_eq!(lines_back(b"0\r"), vec![(0, b"0\r".to_vec())]);
+    assert_eq!(
+        lines_back(b"0\r\n1\r"),
+        vec![(3, b"1\r".to_vec()), (0, b"0".to_vec())]
+    );
+    assert_eq!(
+        lines_back(b"0\n1"),
+        vec![(2, b"1".to_vec()), (0, b"0".to_vec())]
+    );
+    assert_eq!(
+        lines_back(b"0\r\n1\r\n"),
+        vec![(3, b"1".to_vec()), (0, b"0".to_vec())]
+    );
+    assert_eq!(
+        lines_back(b"\n\n"),
+        vec![(1, b"".to_vec()), (0, b"".to_vec())]
+    );
+
+    let long: Vec<u8> = iter::repeat(b'0').take(DEFAULT_CHUNK_SIZE * 2).collect();
+    let bytes: Vec<u8> = long
+        .iter()
+        .chain(b"\r\n".iter())
+        .chain(long.iter())
+        .cloned()
+        .collect();
+    assert_eq!(
+        lines_back(&bytes),
+        vec![
+            (DEFAULT_CHUNK_SIZE as u64 * 2 + 2, long.clone()),
+            (0, long.clone())
+        ]
+    );
+
+    let mut cursor = Cursor::new(&bytes);
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    assert_eq!(
+        seeker.tail(3).unwrap(),
+        vec![
+            (0, long.clone()),
+            (DEFAULT_CHUNK_SIZE as u64 * 2 + 2, long.clone())
+        ]
+    );
+    assert_eq!(seeker.tail(1).unwrap(), vec![]);
+
+    let mut cursor = Cursor::new(b"x\r");
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    assert_eq!(seeker.tail(1).unwrap(), vec![(0, b"x\r".to_vec())]);
+}
+
+#[test]
+fn test_split() {
+    fn split(bytes: &[u8], delimiter: &[u8]) -> Vec<(u64, Vec<u8>)> {
+        let mut cursor = Cursor::new(bytes);
+        let mut seeker = ByteSeeker::new(&mut cursor);
+        seeker.split(delimiter).map(|x| x.unwrap()).collect()
+    }
+
+    fn rsplit(bytes: &[u8], delimiter: &[u8]) -> Vec<(u64, Vec<u8>)> {
+        let mut cursor = Cursor::new(bytes);
+        let mut seeker = ByteSeeker::new(&mut cursor);
+        let mut records: Vec<(u64, Vec<u8>)> =
+            seeker.rsplit(delimiter).map(|x| x.unwrap()).collect();
+        records.reverse();
+        records
+    }
+
+    let record: Vec<u8> = iter::repeat(b'0').take(DEFAULT_CHUNK_SIZE + 1).collect();
+    let mut bytes = Vec::new();
+    for _ in 0..3 {
+        bytes.extend_from_slice(&record);
+        bytes.extend_from_slice(b"\x00\x00END\x00");
+    }
+    let expected: Vec<(u64, Vec<u8>)> = (0..3)
+        .map(|i| (i * (DEFAULT_CHUNK_SIZE as u64 + 7), record.clone()))
+        .collect();
+    assert_eq!(split(&bytes, b"\x00\x00END\x00"), expected);
+    assert_eq!(rsplit(&bytes, b"\x00\x00END\x00"), expected);
+
+    for &(bytes, ref expected) in &[
+        (&b""[..], vec![]),
+        (b",", vec![(0, b"".to_vec())]),
+        (b"0", vec![(0, b"0".to_vec())]),
+        (b"0,", vec![(0, b"0".to_vec())]),
+        (b",0", vec![(0, b"".to_vec()), (1, b"0".to_vec())]),
+        (b",,", vec![(0, b"".to_vec()), (1, b"".to_vec())]),
+    ] {
+        assert_eq!(&split(bytes, b","), expected);
+        assert_eq!(&rsplit(bytes, b","), expected);
+    }
+
+    // Backwards, a self-overlapping delimiter is matched from the end.
+    assert_eq!(
+        split(b"abaaaaaba", b"aa"),
+        vec![(0, b"ab".to_vec()), (4, b"".to_vec()), (6, b"aba".to_vec())]
+    );
+    assert_eq!(
+        rsplit(b"abaaaaaba", b"aa"),
+        vec![(0, b"aba".to_vec()), (5, b"".to_vec()), (7, b"ba".to_vec())]
+    );
+
+    let mut cursor = Cursor::new(b"0");
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    let mut records = seeker.split(b"");
+    match records.next() {
+        Some(Err(e)) => match *e.kind() {
+            ErrorKind::UnsupportedLength => assert!(true),
+            _ => assert!(false),
+        },
+        _ => assert!(false),
+    }
+    assert!(records.next().is_none());
+}
+
+#[test]
+fn test_follow() {
+    use std::fs::{self, File, OpenOptions};
+    use std::io::Write;
+    use std::thread;
+    use std::time::Duration;
+
+    let path = std::env::temp_dir().join(format!("byteseeker-follow-{}", std::process::id()));
+    fs::write(&path, b"0\n1").unwrap();
+
+    let mut seeker = ByteSeeker::new(File::open(&path).unwrap());
+    assert_eq!(seeker.seek(b"\n").unwrap(), 1);
+
+    let writer = {
+        let path = path.clone();
+        thread::spawn(move || {
+            let mut f = OpenOptions::new().append(true).open(path).unwrap();
+            thread::sleep(Duration::from_millis(50));
+            f.write_all(b"\r").unwrap();
+            f.flush().unwrap();
+            thread::sleep(Duration::from_millis(50));
+            f.write_all(b"\n2\r\n").unwrap();
+        })
+    };
+    assert_eq!(
+        seeker
+            .seek_follow(b"\r\n", Duration::from_millis(10))
+            .unwrap(),
+        3
+    );
+    writer.join().unwrap();
+
+    assert_eq!(seeker.refresh().unwrap(), 8);
+    assert_eq!(seeker.seek_back(b"\r\n").unwrap(), 6);
+    assert!(seeker.seek(b"\r\n").is_err());
+
+    // The backward cursor does not hide the appended bytes.
+    let (interval, timeout) = (Duration::from_millis(10), Duration::from_secs(5));
+    OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .unwrap()
+        .write_all(b"3\r\n")
+        .unwrap();
+    assert_eq!(
+        seeker
+            .seek_follow_timeout(b"\r\n", interval, timeout)
+            .unwrap(),
+        6
+    );
+    assert_eq!(
+        seeker
+            .seek_follow_timeout(b"\r\n", interval, timeout)
+            .unwrap(),
+        9
+    );
+
+    // Nothing more is appended.
+    match seeker.seek_follow_timeout(b"\r\n", interval, Duration::from_millis(50)) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+
+    // The stream is truncated, and then grows again, but not beyond its former length.
+    fs::write(&path, b"a").unwrap();
+    let writer = {
+        let path = path.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            let mut f = OpenOptions::new().append(true).open(path).unwrap();
+            f.write_all(b"\r\nb\r\n").unwrap();
+        })
+    };
+    match seeker.seek_follow_timeout(b"\r\n", interval, timeout) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::StreamChanged { expected, actual } => {
+                assert_eq!(expected, 11);
+                assert_eq!(actual, 1);
+            }
+            _ => assert!(false),
+        },
+    }
+    seeker.set_change_policy(ChangePolicy::Restart);
+    assert_eq!(
+        seeker
+            .seek_follow_timeout(b"\r\n", interval, timeout)
+            .unwrap(),
+        1
+    );
+    writer.join().unwrap();
+
+    // A range with a fixed end cannot grow any further.
+    let mut seeker = ByteSeeker::with_range(File::open(&path).unwrap(), 0..3);
+    assert_eq!(seeker.seek_follow(b"\r\n", interval).unwrap(), 1);
+    match seeker.seek_follow(b"\r\n", interval) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+
+    // The stream is rotated to a shorter one, which the change policy applies to.
+    let rotated = |policy: ChangePolicy| {
+        fs::write(&path, b"aaaaaaaaaa\n").unwrap();
+        let mut seeker = ByteSeeker::new(File::open(&path).unwrap());
+        seeker.set_change_policy(policy);
+        assert_eq!(seeker.seek_follow(b"\n", interval).unwrap(), 10);
+        fs::write(&path, b"x\ny\n").unwrap();
+        seeker
+    };
+
+    let mut seeker = rotated(ChangePolicy::Error);
+    match seeker.seek_follow_timeout(b"\n", interval, timeout) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::StreamChanged { expected, actual } => {
+                assert_eq!(expected, 11);
+                assert_eq!(actual, 4);
+            }
+            _ => assert!(false),
+        },
+    }
+    assert_eq!(seeker.len(), 11);
+
+    let mut seeker = rotated(ChangePolicy::Remeasure);
+    match seeker.seek_follow_timeout(b"\n", interval, Duration::from_millis(50)) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+    assert_eq!(seeker.len(), 4);
+    assert_eq!(seeker.position(), 4);
+
+    let mut seeker = rotated(ChangePolicy::Restart);
+    assert_eq!(
+        seeker
+            .seek_follow_timeout(b"\n", interval, timeout)
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        seeker
+            .seek_follow_timeout(b"\n", interval, timeout)
+            .unwrap(),
+        3
+    );
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_stream_changed() {
+    let chunk = DEFAULT_CHUNK_SIZE;
+    let rotated = |policy: ChangePolicy| {
+        let bytes: Vec<u8> = b"\n"
+            .iter()
+            .cloned()
+            .chain(iter::repeat(0).take(chunk * 3))
+            .chain(b"\n".iter().cloned())
+            .collect();
+        let mut seeker = ByteSeeker::new(Cursor::new(bytes));
+        seeker.set_change_policy(policy);
+        assert_eq!(seeker.seek(b"\n").unwrap(), 0);
+
+        // The stream is replaced by a shorter one behind the back of the `ByteSeeker`.
+        let stream = seeker.get_mut().get_mut();
+        stream.clear();
+        stream.extend_from_slice(b"\n");
+        stream.extend(iter::repeat(0).take(chunk));
+        stream.extend_from_slice(b"\n");
+        seeker
+    };
+
+    let mut seeker = rotated(ChangePolicy::Error);
+    match seeker.seek(b"\n") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::StreamChanged { expected, actual } => {
+                assert_eq!(expected, DEFAULT_CHUNK_SIZE as u64 * 3 + 2);
+                assert_eq!(actual, DEFAULT_CHUNK_SIZE as u64 + 2);
+            }
+            _ => assert!(false),
+        },
+    }
+    assert_eq!(seeker.len(), DEFAULT_CHUNK_SIZE as u64 * 3 + 2);
+    assert_eq!(seeker.position(), 1);
+
+    let mut seeker = rotated(ChangePolicy::Remeasure);
+    assert_eq!(seeker.seek(b"\n").unwrap(), DEFAULT_CHUNK_SIZE as u64 + 1);
+    assert_eq!(seeker.len(), DEFAULT_CHUNK_SIZE as u64 + 2);
+
+    let mut seeker = rotated(ChangePolicy::Restart);
+    assert_eq!(seeker.seek(b"\n").unwrap(), 0);
+    assert_eq!(seeker.seek(b"\n").unwrap(), DEFAULT_CHUNK_SIZE as u64 + 1);
+}
+
+#[test]
+fn test_count() {
+    let bytes: Vec<u8> = (0..DEFAULT_CHUNK_SIZE * 5)
+        .map(|i| if i % 7 < 3 { b'\n' } else { b'0' })
+        .collect();
+    let naive = |needle: &[u8], overlapping: bool| {
+        let mut count = 0;
+        let mut i = 0;
+        while i + needle.len() <= bytes.len() {
+            if &bytes[i..i + needle.len()] == needle {
+                count += 1;
+                if !overlapping {
+                    i += needle.len();
+                    continue;
+                }
+            }
+            i += 1;
+        }
+        count
+    };
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    assert_eq!(seeker.count(b"\n").unwrap(), naive(b"\n", false));
+    assert_eq!(seeker.count(b"\n\n").unwrap(), naive(b"\n\n", false));
+    assert_eq!(
+        seeker.count_overlapping(b"\n\n").unwrap(),
+        naive(b"\n\n", true)
+    );
+    assert_eq!(
+        seeker.count(b"0\n\n\n0").unwrap(),
+        naive(b"0\n\n\n0", false)
+    );
+    assert_eq!(seeker.count(b"\r\n").unwrap(), 0);
+
+    // Counting leaves the cursors untouched, and only counts between them.
+    assert_eq!(seeker.seek(b"0").unwrap(), 3);
+    assert_eq!(seeker.count(b"\n").unwrap(), naive(b"\n", false) - 3);
+    assert_eq!(seeker.seek(b"\n").unwrap(), 7);
+
+    match seeker.count(b"") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::UnsupportedLength => assert!(true),
+            _ => assert!(false),
+        },
+    }
+}
+
+#[test]
+fn test_match_mode() {
+    let bytes: Vec<u8> = iter::repeat(b'\n').take(DEFAULT_CHUNK_SIZE + 1).collect();
+    let len = bytes.len() as u64;
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    assert_eq!(seeker.seek_nth(b"\n\n", 2).unwrap(), 2);
+    assert_eq!(seeker.seek_nth_back(b"\n\n", 2).unwrap(), len - 4);
+    seeker.reset();
+    let offsets: Vec<u64> = seeker.find_iter(b"\n\n").map(|x| x.unwrap()).collect();
+    assert_eq!(offsets.len() as u64, len / 2);
+
+    let mut cursor = Cursor::new(bytes);
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    seeker.set_match_mode(MatchMode::Overlapping);
+    assert_eq!(seeker.seek_nth(b"\n\n", 2).unwrap(), 1);
+    assert_eq!(seeker.seek_nth_back(b"\n\n", 2).unwrap(), len - 3);
+    seeker.reset();
+    let offsets: Vec<u64> = seeker.rfind_iter(b"\n\n").map(|x| x.unwrap()).collect();
+    assert_eq!(offsets, (0..len - 1).rev().collect::<Vec<u64>>());
+}
+
+#[test]
+fn test_ascii_case_insensitive() {
+    // The pattern straddles the chunk boundaries in both directions.
+    let bytes: Vec<u8> = iter::repeat(b'0')
+        .take(DEFAULT_CHUNK_SIZE - 3)
+        .chain(b"ConTent-TYPE:".iter().cloned())
+        .chain(iter::repeat(b'0').take(DEFAULT_CHUNK_SIZE - 16))
+        .chain(b"CONTENT-type:".iter().cloned())
+        .chain(iter::repeat(b'0').take(3))
+        .collect();
+
+    let mut cursor = Cursor::new(bytes);
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    match seeker.seek(b"content-type:") {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+
+    seeker.set_ascii_case_insensitive(true);
+    assert_eq!(
+        seeker.seek(b"content-type:").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 - 3
+    );
+    assert_eq!(
+        seeker.seek_back(b"content-type:").unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 * 2 - 6
+    );
+    seeker.reset();
+    assert_eq!(seeker.count(b"content-type:").unwrap(), 2);
+    assert_eq!(seeker.count(b"t").unwrap(), 6);
+    assert_eq!(
+        seeker
+            .seek_any_back(&[b"x", b"content-type:"])
+            .unwrap()
+            .range(),
+        DEFAULT_CHUNK_SIZE as u64 * 2 - 6..DEFAULT_CHUNK_SIZE as u64 * 2 + 7
+    );
+}
+
+#[test]
+fn test_seek_masked() {
+    let pattern: MaskedPattern = "50 4B ?? ?? 14 00".parse().unwrap();
+    // The second header straddles the first chunk boundary.
+    let bytes: Vec<u8> = b"PK\x03\x04\x14\x00"
+        .iter()
+        .cloned()
+        .chain(iter::repeat(b'P').take(DEFAULT_CHUNK_SIZE - 9))
+        .chain(b"PK\x01\x02\x14\x00".iter().cloned())
+        .chain(b"PK\x01\x02\x15\x00".iter().cloned())
+        .collect();
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    assert_eq!(seeker.seek_masked(&pattern).unwrap(), 0);
+    assert_eq!(
+        seeker.seek_masked(&pattern).unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 - 3
+    );
+    match seeker.seek_masked(&pattern) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+
+    let mut cursor = Cursor::new(bytes);
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    assert_eq!(
+        seeker.seek_masked_back(&pattern).unwrap(),
+        DEFAULT_CHUNK_SIZE as u64 - 3
+    );
+    assert_eq!(seeker.seek_masked_back(&pattern).unwrap(), 0);
+
+    match seeker.seek_masked(&MaskedPattern::new(&[], &[])) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::UnsupportedLength => assert!(true),
+            _ => assert!(false),
+        },
+    }
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_seek_regex() {
+    use regex::bytes::Regex;
+
+    let re = Regex::new(r"\d+").unwrap();
+    // Numbers of various lengths, some of them straddling chunk boundaries.
+    let mut bytes = Vec::new();
+    for i in 0..500u64 {
+        bytes.extend_from_slice(format!("{}{}", i * 997, "-".repeat((i % 7) as usize)).as_bytes());
+    }
+    let expected: Vec<_> = re
+        .find_iter(&bytes)
+        .map(|m| m.start() as u64..m.end() as u64)
+        .collect();
+
+    let mut cursor = Cursor::new(bytes.clone());
+    let mut seeker = ByteSeeker::with_capacity(&mut cursor, 16);
+    seeker.set_chunk_size(10);
+    for range in &expected {
+        assert_eq!(&seeker.seek_regex(&re).unwrap().range(), range);
+    }
+    match seeker.seek_regex(&re) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+
+    seeker.reset();
+    for range in expected.iter().rev() {
+        assert_eq!(&seeker.seek_regex_back(&re).unwrap().range(), range);
+    }
+    match seeker.seek_regex_back(&re) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::ByteNotFound => assert!(true),
+            _ => assert!(false),
+        },
+    }
+
+    // The alternative the regular expression prefers wins in either direction.
+    let mut cursor = Cursor::new(b"xabx");
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    let re = Regex::new(r"a|ab").unwrap();
+    assert_eq!(seeker.seek_regex_back(&re).unwrap().range(), 1..2);
+    seeker.reset();
+    let re = Regex::new(r"ab|a").unwrap();
+    assert_eq!(seeker.seek_regex_back(&re).unwrap().range(), 1..3);
+
+    let mut cursor = Cursor::new(b"xabx");
+    let mut seeker = ByteSeeker::with_capacity(&mut cursor, usize::MAX);
+    assert_eq!(seeker.seek_regex(&re).unwrap().range(), 1..3);
+    seeker.reset();
+    assert_eq!(seeker.seek_regex_back(&re).unwrap().range(), 1..3);
+
+    // Anchors match at the cursor, whatever precedes it.
+    let mut cursor = Cursor::new(b"xfoo foo");
+    let mut seeker = ByteSeeker::new(&mut cursor);
+    let re = Regex::new(r"\bfoo").unwrap();
+    assert_eq!(seeker.seek(b"x").unwrap(), 0);
+    assert_eq!(seeker.seek_regex(&re).unwrap().range(), 1..4);
+    assert_eq!(seeker.seek_regex(&re).unwrap().range(), 5..8);
+
+    let mut cursor = Cursor::new(b"xabx");
+    let mut seeker = ByteSeeker::with_capacity(&mut cursor, 0);
+    match seeker.seek_regex(&re) {
+        Ok(_) => assert!(false),
+        Err(e) => match *e.kind() {
+            ErrorKind::UnsupportedLength => assert!(true),
+            _ => assert!(false),
+        },
+    }
+}